@@ -42,10 +42,34 @@ fn arithmetic(c: &mut Criterion) {
     {
         let name = format!("{}/{}", name(x), name(y));
         c.bench_with_input(
-            BenchmarkId::new("U256::div", name),
+            BenchmarkId::new("U256::div", &name),
             &(x, y),
             |b, &(x, y)| b.iter(|| black_box(x) / black_box(y)),
         );
+
+        #[cfg(not(feature = "primitive-types"))]
+        c.bench_with_input(
+            BenchmarkId::new("U256::div_divisor", &name),
+            &(x, y.divisor()),
+            |b, &(x, y)| b.iter(|| black_box(x) / black_box(y)),
+        );
+    }
+
+    for x in q {
+        c.bench_with_input(
+            BenchmarkId::new("U256::div_small", name(x)),
+            &(x, U256::from(10_000_u64)),
+            |b, &(x, y)| b.iter(|| black_box(x) / black_box(y)),
+        );
+    }
+
+    #[cfg(not(feature = "primitive-types"))]
+    for x in q {
+        c.bench_with_input(
+            BenchmarkId::new("U256::div_rem_u64", name(x)),
+            &x,
+            |b, &x| b.iter(|| black_box(x).div_rem_u64(black_box(10_000))),
+        );
     }
 
     c.bench_function("U256::mul", |b| {
@@ -99,5 +123,44 @@ fn arithmetic(c: &mut Criterion) {
     }
 }
 
-criterion_group!(num, arithmetic);
+fn formatting(c: &mut Criterion) {
+    let x = (U256::from(0x00017eb02a11f4a9443abc5058e1c2c2_u128) << 128_u32)
+        + U256::from(0x3540ba08c848a6eb3a1e1415b0000000_u128);
+
+    c.bench_function("U256::to_string", |b| b.iter(|| black_box(x).to_string()));
+
+    c.bench_function("U256::fmt_hex", |b| {
+        b.iter(|| format!("{:x}", black_box(x)))
+    });
+
+    #[cfg(not(feature = "primitive-types"))]
+    c.bench_function("U256::to_be_bytes", |b| {
+        b.iter(|| black_box(x).to_be_bytes())
+    });
+}
+
+#[cfg(not(feature = "primitive-types"))]
+fn parsing(c: &mut Criterion) {
+    let x = (U256::from(0x00017eb02a11f4a9443abc5058e1c2c2_u128) << 128_u32)
+        + U256::from(0x3540ba08c848a6eb3a1e1415b0000000_u128);
+    let dec = x.to_string();
+    let hex = format!("{:#x}", x);
+
+    c.bench_function("U256::from_str", |b| {
+        b.iter(|| black_box(&dec).parse::<U256>())
+    });
+
+    c.bench_function("U256::from_str_hex", |b| {
+        b.iter(|| U256::from_str_hex(black_box(&hex)))
+    });
+
+    c.bench_function("U256::from_str_short", |b| {
+        b.iter(|| black_box("1337").parse::<U256>())
+    });
+}
+
+#[cfg(feature = "primitive-types")]
+fn parsing(_: &mut Criterion) {}
+
+criterion_group!(num, arithmetic, formatting, parsing);
 criterion_main!(num);
@@ -0,0 +1,14 @@
+#!/bin/sh
+
+if [ ! -d '.git' ]; then
+    echo "ERROR: must be run from repository root" 1>&2
+    exit 1
+fi
+
+# Compares the `safe` feature against the default build over the arithmetic,
+# formatting and parsing paths that have separate safe implementations.
+filter='U256::(div|mul|wrapping_mul|to_string|fmt_hex|to_be_bytes|from_str)'
+
+cd bench || exit 1
+cargo bench -q --bench num -- --save-baseline default "$@" "$filter" || exit 1
+cargo bench -q --bench num --features safe -- --baseline default "$@" "$filter"
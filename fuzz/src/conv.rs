@@ -5,10 +5,9 @@ use ethnum::{I256, U256};
 use num::{bigint::Sign, BigInt};
 
 /// Trait with common integer methods used for [`num::BigInt`] conversions.
-pub trait Int: Sized {
+pub trait Int: Copy + Into<BigInt> {
     fn from_nibbles(n: [u64; 4]) -> (Self, bool);
     fn neg(&self) -> (Self, bool);
-    fn le(&self) -> ([u8; 32], bool);
 }
 
 macro_rules! impl_int {
@@ -24,10 +23,6 @@ macro_rules! impl_int {
             fn neg(&self) -> (Self, bool) {
                 self.overflowing_neg()
             }
-
-            fn le(&self) -> ([u8; 32], bool) {
-                (self.to_le_bytes(), *self < 0)
-            }
         }
     )*};
 }
@@ -61,14 +56,7 @@ pub fn to_bigint<T>(n: &T) -> BigInt
 where
     T: Int,
 {
-    let (bytes, negative) = n.le();
-    let sign = if negative { 0xff } else { 0 };
-
-    let mut buffer = [0_u8; 33];
-    buffer[..32].copy_from_slice(&bytes);
-    buffer[32] = sign;
-
-    BigInt::from_signed_bytes_le(&buffer)
+    (*n).into()
 }
 
 #[cfg(test)]
@@ -118,7 +106,7 @@ mod tests {
         assert_eq!(to_bigint(&U256::ONE), BigInt::one());
         assert_eq!(to_bigint(&U256::MAX), (BigInt::one() << 256) - 1);
 
-        //assert_eq!(to_bigint(&I256::MIN), -(BigInt::one() << 255));
+        assert_eq!(to_bigint(&I256::MIN), -(BigInt::one() << 255_u32));
         assert_eq!(to_bigint(&I256::MINUS_ONE), -BigInt::one());
         assert_eq!(to_bigint(&I256::ZERO), BigInt::zero());
         assert_eq!(to_bigint(&I256::ONE), BigInt::one());
@@ -0,0 +1,72 @@
+use ethnum::{serde::number, I256, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Debug;
+
+struct Number<T>(T);
+
+impl<T: number::Number> Serialize for Number<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        number::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: number::Number> Deserialize<'de> for Number<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number::deserialize(deserializer).map(Number)
+    }
+}
+
+fn round_trip<T>(value: T, json: &str)
+where
+    T: number::Number + Copy + PartialEq + Debug,
+{
+    assert_eq!(serde_json::to_string(&Number(value)).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Number<T>>(json).unwrap().0, value);
+}
+
+#[test]
+fn round_trips() {
+    round_trip(U256::ZERO, "0");
+    round_trip(U256::new(42), "42");
+    round_trip(
+        U256::new(u128::MAX),
+        "340282366920938463463374607431768211455",
+    );
+    round_trip(
+        U256::MAX,
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+    );
+    round_trip(I256::new(-42), "-42");
+    round_trip(I256::new(u64::MAX.into()), "18446744073709551615");
+    round_trip(
+        I256::new(i128::MIN),
+        "-170141183460469231731687303715884105728",
+    );
+    round_trip(
+        I256::MIN,
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+    );
+}
+
+#[test]
+fn rejects_invalid_numbers() {
+    for json in [
+        "-1",
+        "1.5",
+        "1e3",
+        "\"42\"",
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+    ] {
+        assert!(serde_json::from_str::<Number<U256>>(json).is_err());
+    }
+    assert!(serde_json::from_str::<Number<I256>>(
+        "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+    )
+    .is_err());
+}
@@ -0,0 +1,280 @@
+//! Solidity ABI encoding and decoding for `uintN` and `intN` types.
+//!
+//! Values are encoded as 32-byte big endian words as specified in the Solidity
+//! contract ABI specification:
+//! <https://docs.soliditylang.org/en/latest/abi-spec.html>
+//!
+//! Signed integers narrower than 256 bits are sign-extended to fill the word,
+//! and unsigned integers are zero-padded. Decoding is strict in that words
+//! with "dirty" high bits (i.e. bits that are not a valid zero or sign
+//! extension for the specified width) are rejected.
+//!
+//! Additionally, this module supports the non-standard packed mode used by
+//! `abi.encodePacked`, where integers are encoded in exactly `N / 8` bytes.
+
+use crate::{int::I256, uint::U256};
+use core::fmt::{self, Display, Formatter};
+
+/// The size of an ABI encoded word in bytes.
+pub const WORD_SIZE: usize = 32;
+
+/// An ABI encoding or decoding error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The specified bit width is not a multiple of 8 in the range `8..=256`.
+    InvalidBits(u32),
+    /// The value does not fit in the specified bit width.
+    OutOfRange,
+    /// The encoded word has high bits that are not a valid zero or sign
+    /// extension for the specified bit width.
+    DirtyBits,
+    /// The output buffer is too small to hold the packed encoding.
+    BufferTooSmall,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidBits(bits) => write!(f, "invalid ABI integer bit width {bits}"),
+            Self::OutOfRange => f.write_str("value out of range for ABI integer type"),
+            Self::DirtyBits => f.write_str("ABI word has dirty high bits"),
+            Self::BufferTooSmall => f.write_str("buffer too small for packed ABI encoding"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Verifies that `bits` is a valid ABI integer bit width.
+#[inline]
+fn check_bits(bits: u32) -> Result<(), Error> {
+    if bits == 0 || bits > 256 || bits & 7 != 0 {
+        return Err(Error::InvalidBits(bits));
+    }
+    Ok(())
+}
+
+/// Returns `true` if the unsigned value fits in `bits` bits.
+#[inline]
+fn uint_fits(value: U256, bits: u32) -> bool {
+    bits == 256 || value >> bits == 0
+}
+
+/// Returns `true` if the signed value fits in `bits` bits.
+#[inline]
+fn int_fits(value: I256, bits: u32) -> bool {
+    let shift = 256 - bits;
+    (value << shift) >> shift == value
+}
+
+/// Encodes a `uintN` value as a 32-byte ABI word.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, U256};
+/// let word = abi::encode_uint(U256::new(0x1337), 16).unwrap();
+/// assert_eq!(word[30..], [0x13, 0x37]);
+/// assert!(word[..30].iter().all(|&b| b == 0));
+///
+/// assert_eq!(
+///     abi::encode_uint(U256::new(256), 8),
+///     Err(abi::Error::OutOfRange),
+/// );
+/// ```
+pub fn encode_uint(value: U256, bits: u32) -> Result<[u8; WORD_SIZE], Error> {
+    check_bits(bits)?;
+    if !uint_fits(value, bits) {
+        return Err(Error::OutOfRange);
+    }
+    Ok(value.to_be_bytes())
+}
+
+/// Encodes an `intN` value as a sign-extended 32-byte ABI word.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, I256};
+/// assert_eq!(abi::encode_int(I256::new(-1), 8).unwrap(), [0xff; 32]);
+/// assert_eq!(
+///     abi::encode_int(I256::new(128), 8),
+///     Err(abi::Error::OutOfRange),
+/// );
+/// ```
+pub fn encode_int(value: I256, bits: u32) -> Result<[u8; WORD_SIZE], Error> {
+    check_bits(bits)?;
+    if !int_fits(value, bits) {
+        return Err(Error::OutOfRange);
+    }
+    Ok(value.to_be_bytes())
+}
+
+/// Decodes a `uintN` value from a 32-byte ABI word.
+///
+/// Words with non-zero bits above the specified width are rejected.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, U256};
+/// let mut word = [0; 32];
+/// word[31] = 42;
+/// assert_eq!(abi::decode_uint(&word, 8), Ok(U256::new(42)));
+///
+/// word[30] = 1;
+/// assert_eq!(abi::decode_uint(&word, 8), Err(abi::Error::DirtyBits));
+/// ```
+pub fn decode_uint(word: &[u8; WORD_SIZE], bits: u32) -> Result<U256, Error> {
+    check_bits(bits)?;
+    let value = U256::from_be_bytes(*word);
+    if !uint_fits(value, bits) {
+        return Err(Error::DirtyBits);
+    }
+    Ok(value)
+}
+
+/// Decodes an `intN` value from a 32-byte ABI word.
+///
+/// Words whose bits above the specified width are not a sign extension of the
+/// value are rejected.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, I256};
+/// assert_eq!(abi::decode_int(&[0xff; 32], 8), Ok(I256::new(-1)));
+///
+/// let mut word = [0; 32];
+/// word[31] = 0x80;
+/// assert_eq!(abi::decode_int(&word, 8), Err(abi::Error::DirtyBits));
+/// ```
+pub fn decode_int(word: &[u8; WORD_SIZE], bits: u32) -> Result<I256, Error> {
+    check_bits(bits)?;
+    let value = I256::from_be_bytes(*word);
+    if !int_fits(value, bits) {
+        return Err(Error::DirtyBits);
+    }
+    Ok(value)
+}
+
+/// Encodes a `uintN` value in packed mode (as used by `abi.encodePacked`) to
+/// the start of the specified buffer, returning the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, U256};
+/// let mut buffer = [0; 32];
+/// let len = abi::encode_packed_uint(U256::new(0x123456), 24, &mut buffer).unwrap();
+/// assert_eq!(buffer[..len], [0x12, 0x34, 0x56]);
+/// ```
+pub fn encode_packed_uint(value: U256, bits: u32, buffer: &mut [u8]) -> Result<usize, Error> {
+    let word = encode_uint(value, bits)?;
+    write_packed(&word, bits, buffer)
+}
+
+/// Encodes an `intN` value in packed mode (as used by `abi.encodePacked`) to
+/// the start of the specified buffer, returning the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{abi, I256};
+/// let mut buffer = [0; 32];
+/// let len = abi::encode_packed_int(I256::new(-2), 16, &mut buffer).unwrap();
+/// assert_eq!(buffer[..len], [0xff, 0xfe]);
+/// ```
+pub fn encode_packed_int(value: I256, bits: u32, buffer: &mut [u8]) -> Result<usize, Error> {
+    let word = encode_int(value, bits)?;
+    write_packed(&word, bits, buffer)
+}
+
+/// Writes the low `bits / 8` bytes of an encoded word to a buffer.
+#[inline]
+fn write_packed(word: &[u8; WORD_SIZE], bits: u32, buffer: &mut [u8]) -> Result<usize, Error> {
+    let len = (bits / 8) as usize;
+    let buffer = buffer.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+    buffer.copy_from_slice(&word[WORD_SIZE - len..]);
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_bits() {
+        for bits in [0, 7, 9, 255, 264] {
+            assert_eq!(encode_uint(U256::ZERO, bits), Err(Error::InvalidBits(bits)));
+            assert_eq!(encode_int(I256::ZERO, bits), Err(Error::InvalidBits(bits)));
+            assert_eq!(decode_uint(&[0; 32], bits), Err(Error::InvalidBits(bits)));
+            assert_eq!(decode_int(&[0; 32], bits), Err(Error::InvalidBits(bits)));
+        }
+    }
+
+    #[test]
+    fn uint_ranges() {
+        for bits in (8..=256).step_by(8) {
+            let max = U256::MAX >> (256 - bits);
+            let word = encode_uint(max, bits).unwrap();
+            assert_eq!(decode_uint(&word, bits), Ok(max));
+
+            if bits < 256 {
+                assert_eq!(encode_uint(max + 1, bits), Err(Error::OutOfRange));
+                let word = (max + 1).to_be_bytes();
+                assert_eq!(decode_uint(&word, bits), Err(Error::DirtyBits));
+            }
+        }
+    }
+
+    #[test]
+    fn int_ranges() {
+        for bits in (8..=256).step_by(8) {
+            let max = I256::MAX >> (256 - bits);
+            let min = I256::MIN >> (256 - bits);
+            for value in [max, min, I256::ZERO, I256::MINUS_ONE] {
+                let word = encode_int(value, bits).unwrap();
+                assert_eq!(word, value.to_be_bytes());
+                assert_eq!(decode_int(&word, bits), Ok(value));
+            }
+
+            if bits < 256 {
+                assert_eq!(encode_int(max + 1, bits), Err(Error::OutOfRange));
+                assert_eq!(encode_int(min - 1, bits), Err(Error::OutOfRange));
+                assert_eq!(
+                    decode_int(&(max + 1).to_be_bytes(), bits),
+                    Err(Error::DirtyBits),
+                );
+                assert_eq!(
+                    decode_int(&(min - 1).to_be_bytes(), bits),
+                    Err(Error::DirtyBits),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn packed() {
+        let mut buffer = [0; 32];
+        for bits in (8..=256).step_by(8) {
+            let len = encode_packed_uint(U256::ONE, bits, &mut buffer).unwrap();
+            assert_eq!(len, bits as usize / 8);
+            assert_eq!(buffer[len - 1], 1);
+            assert!(buffer[..len - 1].iter().all(|&b| b == 0));
+
+            let len = encode_packed_int(I256::MINUS_ONE, bits, &mut buffer).unwrap();
+            assert_eq!(len, bits as usize / 8);
+            assert!(buffer[..len].iter().all(|&b| b == 0xff));
+        }
+
+        assert_eq!(
+            encode_packed_uint(U256::ONE, 32, &mut [0; 3]),
+            Err(Error::BufferTooSmall),
+        );
+        assert_eq!(
+            encode_packed_int(I256::new(-129), 8, &mut buffer),
+            Err(Error::OutOfRange),
+        );
+    }
+}
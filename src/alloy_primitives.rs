@@ -0,0 +1,54 @@
+//! Conversions between 256-bit signed integers and `alloy-primitives`.
+//!
+//! Conversions for `alloy_primitives::U256` are provided by the `ruint`
+//! integration, since it is an alias for `ruint::Uint<256, 4>`. Signed
+//! integers use the same two's complement representation, so conversions are
+//! simple reinterpretations that do not go through byte arrays:
+//!
+//! ```
+//! # use ethnum::{I256, U256};
+//! let value = alloy_primitives::I256::MINUS_ONE;
+//! assert_eq!(I256::from(value), -1);
+//! let value: alloy_primitives::I256 = I256::MIN.into();
+//! assert_eq!(value, alloy_primitives::I256::MIN);
+//!
+//! let value = alloy_primitives::U256::from(42);
+//! assert_eq!(U256::from(value), 42);
+//! ```
+
+use crate::int::I256;
+use ::alloy_primitives::Signed;
+
+impl From<Signed<256, 4>> for I256 {
+    #[inline]
+    fn from(value: Signed<256, 4>) -> Self {
+        crate::uint::U256::from(value.into_raw()).as_i256()
+    }
+}
+
+impl From<I256> for Signed<256, 4> {
+    #[inline]
+    fn from(value: I256) -> Self {
+        Signed::from_raw(value.as_u256().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            I256::MIN,
+            I256::new(-42),
+            I256::ZERO,
+            I256::from_words(0x0123456789abcdef, -42),
+            I256::MAX,
+        ] {
+            let converted: Signed<256, 4> = value.into();
+            assert_eq!(converted.to_be_bytes::<32>(), value.to_be_bytes());
+            assert_eq!(I256::from(converted), value);
+        }
+    }
+}
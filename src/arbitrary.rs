@@ -0,0 +1,48 @@
+//! Implementation of `arbitrary::Arbitrary` for 256-bit integer types.
+//!
+//! Integers are built from 32 little endian bytes, in the same way as the
+//! `arbitrary` crate builds primitive integers. This means that exhausted
+//! input data produces zero.
+
+use crate::{int::I256, uint::U256};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+/// The number of bytes consumed for a 256-bit integer.
+const BYTES_LEN: usize = 32;
+
+macro_rules! impl_arbitrary {
+    ($($int:ident),*) => {$(
+        impl<'a> Arbitrary<'a> for $int {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let mut bytes = [0; BYTES_LEN];
+                u.fill_buffer(&mut bytes)?;
+                Ok(Self::from_le_bytes(bytes))
+            }
+
+            #[inline]
+            fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+                (BYTES_LEN, Some(BYTES_LEN))
+            }
+        }
+    )*};
+}
+
+impl_arbitrary! { I256, U256 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes() {
+        let mut data = [0; 64];
+        data[0] = 42;
+        data[32..].fill(0xff);
+
+        let mut u = Unstructured::new(&data);
+        assert_eq!(U256::arbitrary(&mut u).unwrap(), 42);
+        assert_eq!(I256::arbitrary(&mut u).unwrap(), -1);
+        assert_eq!(U256::arbitrary(&mut u).unwrap(), 0);
+        assert_eq!(U256::size_hint(0), (32, Some(32)));
+    }
+}
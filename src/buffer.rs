@@ -0,0 +1,170 @@
+//! Module implementing a stack-allocated string buffer.
+//!
+//! This allows formatting integers in `no_std` environments without an
+//! allocator:
+//!
+//! ```
+//! # use ethnum::{FormatBuffer, U256};
+//! use core::fmt::Write as _;
+//!
+//! let mut buf = FormatBuffer::<80>::new();
+//! write!(buf, "{} wei", U256::new(42)).unwrap();
+//! assert_eq!(buf.as_str(), "42 wei");
+//!
+//! assert_eq!(U256::new(255).to_str_radix_buf(2).as_str(), "11111111");
+//! ```
+
+use crate::uint::U256;
+use core::{
+    fmt::{self, Debug, Display, Formatter, Write},
+    ops::Deref,
+    str,
+};
+
+/// A stack-allocated buffer of `N` bytes that can be used for writing
+/// formatted strings.
+///
+/// This allows leveraging existing `fmt` implementations on integer types
+/// without requiring heap allocations (i.e. writing to a `String` buffer).
+/// Writes that exceed the capacity of the buffer fail with a [`fmt::Error`].
+#[derive(Clone, Copy)]
+pub struct FormatBuffer<const N: usize> {
+    offset: usize,
+    buffer: [u8; N],
+}
+
+impl<const N: usize> FormatBuffer<N> {
+    /// Creates a new empty formatting buffer.
+    pub const fn new() -> Self {
+        Self {
+            offset: 0,
+            buffer: [0; N],
+        }
+    }
+
+    /// Returns a `str` to the currently written data.
+    pub fn as_str(&self) -> &str {
+        let buffer = self.as_bytes();
+        #[cfg(not(feature = "safe"))]
+        // SAFETY: We only ever write valid UTF-8 strings to the buffer, so the
+        // resulting string will always be valid.
+        return unsafe { str::from_utf8_unchecked(buffer) };
+        #[cfg(feature = "safe")]
+        return str::from_utf8(buffer).expect("buffer is valid UTF-8");
+    }
+
+    /// Returns the bytes of the currently written data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.offset]
+    }
+
+    /// Returns the length of the currently written data in bytes.
+    pub const fn len(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns `true` if no data has been written to the buffer.
+    pub const fn is_empty(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Returns the total number of bytes the buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Clears the buffer, removing all written data.
+    pub fn clear(&mut self) {
+        self.offset = 0;
+    }
+}
+
+impl<const N: usize> FormatBuffer<N> {
+    /// Creates a buffer containing an integer formatted in the specified
+    /// radix, with a leading `-` sign if it is negative.
+    pub(crate) fn radix(x: U256, negative: bool, radix: u32) -> Self {
+        let mut result = Self::new();
+        let mut start = crate::fmt::fmt_radix(x, radix, &mut result.buffer);
+        if negative {
+            start -= 1;
+            result.buffer[start] = b'-';
+        }
+        result.buffer.copy_within(start.., 0);
+        result.offset = N - start;
+        result
+    }
+}
+
+impl<const N: usize> Default for FormatBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for FormatBuffer<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for FormatBuffer<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Debug for FormatBuffer<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for FormatBuffer<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> Write for FormatBuffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.offset.checked_add(s.len()).ok_or(fmt::Error)?;
+
+        // Make sure there is enough space in the buffer.
+        if end > N {
+            return Err(fmt::Error);
+        }
+
+        self.buffer[self.offset..end].copy_from_slice(s.as_bytes());
+        self.offset = end;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let mut buf = FormatBuffer::<8>::new();
+        assert!(buf.is_empty());
+
+        write!(buf, "{}-{:x}", 12, 10).unwrap();
+        buf.write_char('b').unwrap();
+        assert_eq!(buf.as_str(), "12-ab");
+        assert_eq!(buf.len(), 5);
+
+        assert!(buf.write_str("four").is_err());
+        assert_eq!(&*buf, "12-ab");
+
+        buf.write_str("éa").unwrap();
+        assert_eq!(buf.as_bytes(), "12-abéa".as_bytes());
+
+        buf.clear();
+        assert_eq!(buf.as_str(), "");
+        assert_eq!(buf.capacity(), 8);
+    }
+}
@@ -0,0 +1,44 @@
+//! Implementation of `bytemuck` traits for 256-bit integer types.
+//!
+//! This allows casting slices of integers to and from raw bytes without
+//! copying, for example when reading from memory mapped files:
+//!
+//! ```
+//! # use ethnum::U256;
+//! let values = [U256::ONE, U256::MAX];
+//! let bytes: &[u8] = bytemuck::cast_slice(&values);
+//! assert_eq!(bytes.len(), 64);
+//! assert_eq!(bytemuck::pod_read_unaligned::<U256>(&bytes[32..]), U256::MAX);
+//! ```
+
+use crate::{int::I256, uint::U256};
+use ::bytemuck::{Pod, Zeroable};
+
+// SAFETY: Both integer types are transparent wrappers around arrays of
+// primitive integers, for which the all-zero bit pattern is valid.
+unsafe impl Zeroable for I256 {}
+unsafe impl Zeroable for U256 {}
+
+// SAFETY: Both integer types are `Copy`, `'static`, transparent wrappers
+// around arrays of primitive integers, so they have no padding and every bit
+// pattern is valid.
+unsafe impl Pod for I256 {}
+unsafe impl Pod for U256 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casts() {
+        assert_eq!(U256::zeroed(), U256::ZERO);
+        assert_eq!(I256::zeroed(), I256::ZERO);
+
+        let bytes = [0xff_u8; 32];
+        assert_eq!(*::bytemuck::from_bytes::<I256>(&bytes), I256::MINUS_ONE);
+        assert_eq!(::bytemuck::pod_read_unaligned::<U256>(&bytes), U256::MAX);
+
+        let value = U256::from_words(1, 2);
+        assert_eq!(::bytemuck::bytes_of(&value), &value.to_ne_bytes());
+    }
+}
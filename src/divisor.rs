@@ -0,0 +1,399 @@
+//! Module implementing division by invariant divisors.
+//!
+//! Dividing many values by the same divisor can be done significantly faster
+//! by precomputing a reciprocal of the divisor once:
+//!
+//! ```
+//! # use ethnum::U256;
+//! let total_supply = U256::new(21_000_000).divisor();
+//! let balances = [U256::new(1_000_000_000), U256::new(42_000_000)];
+//!
+//! let shares = balances.map(|balance| balance / total_supply);
+//! assert_eq!(shares, [U256::new(47), U256::new(2)]);
+//! ```
+
+use crate::uint::U256;
+use core::ops::{Div, Rem};
+
+/// A non-zero divisor with a precomputed reciprocal, for efficiently dividing
+/// many [`U256`] values by the same divisor.
+///
+/// Creating a divisor normalises it and precomputes a reciprocal of its most
+/// significant 64-bit limbs, following the method from Möller and Granlund's
+/// "Improved division by invariant integers". Each division then estimates
+/// quotient digits with multiplications only, instead of the normalisation
+/// and trial divisions performed by the generic [`U256`] division.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{Divisor256, U256};
+/// let price = Divisor256::new(U256::new(1_234_567));
+/// assert_eq!(U256::new(10_000_000) / price, 8);
+/// assert_eq!(U256::new(10_000_000) % price, 123_464);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divisor256 {
+    divisor: U256,
+    /// The divisor shifted left so that its most significant bit is set, as
+    /// little-endian 64-bit limbs.
+    normalized: [u64; 4],
+    /// The number of significant limbs in the divisor.
+    len: usize,
+    /// The number of bits the divisor was shifted by for normalisation.
+    shift: u32,
+    /// The reciprocal of the most significant normalised limb for single limb
+    /// divisors, or of the two most significant normalised limbs otherwise.
+    reciprocal: u64,
+}
+
+impl Divisor256 {
+    /// Creates a new divisor, precomputing its reciprocal.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `divisor` is 0.
+    pub fn new(divisor: U256) -> Self {
+        assert!(divisor != 0, "attempt to divide by zero");
+
+        let zeros = divisor.leading_zeros();
+        let shift = zeros % 64;
+        let normalized = (divisor << shift).into_limbs();
+        let len = 4 - (zeros / 64) as usize;
+        let reciprocal = if len == 1 {
+            reciprocal_2by1(normalized[0])
+        } else {
+            reciprocal_3by2(normalized[len - 1], normalized[len - 2])
+        };
+
+        Self {
+            divisor,
+            normalized,
+            len,
+            shift,
+            reciprocal,
+        }
+    }
+
+    /// Returns the value of the divisor.
+    pub const fn get(&self) -> U256 {
+        self.divisor
+    }
+
+    /// Computes the quotient and remainder of `x` divided by this divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let d = U256::new(10).divisor();
+    /// assert_eq!(d.div_rem(U256::new(42)), (U256::new(4), U256::new(2)));
+    /// ```
+    pub fn div_rem(&self, x: U256) -> (U256, U256) {
+        if x < self.divisor {
+            return (U256::ZERO, x);
+        }
+        if let (0, x) = x.into_words() {
+            // The divisor is at most the dividend, so both fit in 128 bits
+            // where native division is already as fast.
+            let d = *self.divisor.low();
+            return (U256::new(x / d), U256::new(x % d));
+        }
+
+        // Normalise the dividend with the same shift as the divisor, which
+        // may overflow into an additional limb. Only the significant limbs of
+        // the dividend need to be divided.
+        let m = 4 - (x.leading_zeros() / 64) as usize;
+        let s = self.shift;
+        let x = x.into_limbs();
+        let mut u = [0_u64; 5];
+        if s == 0 {
+            u[..4].copy_from_slice(&x);
+        } else {
+            for (i, x) in x.into_iter().enumerate() {
+                u[i] |= x << s;
+                u[i + 1] = x >> (64 - s);
+            }
+        }
+
+        let n = self.len;
+        let d = &self.normalized;
+        let v = self.reciprocal;
+        let mut q = [0_u64; 4];
+
+        if n == 1 {
+            let mut r = u[m];
+            for i in (0..m).rev() {
+                (q[i], r) = div_2by1(r, u[i], d[0], v);
+            }
+            return (U256::from_limbs(q), U256::new((r >> s) as _));
+        }
+
+        let (d1, d0) = (d[n - 1], d[n - 2]);
+        for j in (0..=m - n).rev() {
+            let window = &mut u[j..=j + n];
+            let (n2, n1, n0) = (window[n], window[n - 1], window[n - 2]);
+            // The quotient digit estimate from the three most significant
+            // limbs is either exact or one too large. When the most
+            // significant limbs are equal to the divisor's, the digit is
+            // exactly the largest possible limb.
+            let mut qhat = if n2 == d1 && n1 == d0 {
+                u64::MAX
+            } else {
+                div_3by2(n2, n1, n0, d1, d0, v)
+            };
+            if submul(window, &d[..n], qhat) {
+                qhat -= 1;
+                add_back(window, &d[..n]);
+            }
+            q[j] = qhat;
+        }
+
+        let r = U256::from_limbs([u[0], u[1], u[2], u[3]]) >> s;
+        (U256::from_limbs(q), r)
+    }
+}
+
+impl From<U256> for Divisor256 {
+    fn from(divisor: U256) -> Self {
+        Self::new(divisor)
+    }
+}
+
+impl Div<Divisor256> for U256 {
+    type Output = U256;
+
+    #[inline]
+    fn div(self, rhs: Divisor256) -> Self::Output {
+        rhs.div_rem(self).0
+    }
+}
+
+impl Div<&'_ Divisor256> for U256 {
+    type Output = U256;
+
+    #[inline]
+    fn div(self, rhs: &Divisor256) -> Self::Output {
+        rhs.div_rem(self).0
+    }
+}
+
+impl Rem<Divisor256> for U256 {
+    type Output = U256;
+
+    #[inline]
+    fn rem(self, rhs: Divisor256) -> Self::Output {
+        rhs.div_rem(self).1
+    }
+}
+
+impl Rem<&'_ Divisor256> for U256 {
+    type Output = U256;
+
+    #[inline]
+    fn rem(self, rhs: &Divisor256) -> Self::Output {
+        rhs.div_rem(self).1
+    }
+}
+
+/// Computes the reciprocal `⌊(β² - 1) / d⌋ - β` of a normalised limb, where
+/// `β = 2⁶⁴`.
+#[inline]
+fn reciprocal_2by1(d: u64) -> u64 {
+    debug_assert!(d >> 63 == 1);
+    (u128::MAX / d as u128) as u64
+}
+
+/// Computes the reciprocal `⌊(β³ - 1) / d⌋ - β` of a normalised two limb
+/// divisor `d = d₁β + d₀`, where `β = 2⁶⁴`.
+#[inline]
+fn reciprocal_3by2(d1: u64, d0: u64) -> u64 {
+    let mut v = reciprocal_2by1(d1);
+    let mut p = d1.wrapping_mul(v).wrapping_add(d0);
+    if p < d0 {
+        v = v.wrapping_sub(1);
+        if p >= d1 {
+            v = v.wrapping_sub(1);
+            p = p.wrapping_sub(d1);
+        }
+        p = p.wrapping_sub(d1);
+    }
+
+    let t = d0 as u128 * v as u128;
+    let (t1, t0) = ((t >> 64) as u64, t as u64);
+    p = p.wrapping_add(t1);
+    if p < t1 {
+        v = v.wrapping_sub(1);
+        if p > d1 || (p == d1 && t0 >= d0) {
+            v = v.wrapping_sub(1);
+        }
+    }
+    v
+}
+
+/// Divides `u₁β + u₀` by a normalised limb `d` with reciprocal `v`, returning
+/// the quotient and remainder. Requires `u₁ < d`.
+#[inline]
+fn div_2by1(u1: u64, u0: u64, d: u64, v: u64) -> (u64, u64) {
+    debug_assert!(u1 < d);
+    let q = (v as u128 * u1 as u128).wrapping_add((u1 as u128) << 64 | u0 as u128);
+    let mut q1 = ((q >> 64) as u64).wrapping_add(1);
+    let q0 = q as u64;
+    let mut r = u0.wrapping_sub(q1.wrapping_mul(d));
+    if r > q0 {
+        q1 = q1.wrapping_sub(1);
+        r = r.wrapping_add(d);
+    }
+    if r >= d {
+        q1 += 1;
+        r -= d;
+    }
+    (q1, r)
+}
+
+/// Divides `n₂β² + n₁β + n₀` by a normalised two limb divisor `d₁β + d₀` with
+/// reciprocal `v`, returning the quotient. Requires `n₂β + n₁ < d₁β + d₀`.
+#[inline]
+fn div_3by2(n2: u64, n1: u64, n0: u64, d1: u64, d0: u64, v: u64) -> u64 {
+    let d = (d1 as u128) << 64 | d0 as u128;
+    debug_assert!(((n2 as u128) << 64 | n1 as u128) < d);
+
+    let q = (n2 as u128 * v as u128).wrapping_add((n2 as u128) << 64 | n1 as u128);
+    let (q1, q0) = ((q >> 64) as u64, q as u64);
+    let r1 = n1.wrapping_sub(d1.wrapping_mul(q1));
+    let mut r = ((r1 as u128) << 64 | n0 as u128)
+        .wrapping_sub(d)
+        .wrapping_sub(d0 as u128 * q1 as u128);
+    let mut q1 = q1.wrapping_add(1);
+    if (r >> 64) as u64 >= q0 {
+        q1 = q1.wrapping_sub(1);
+        r = r.wrapping_add(d);
+    }
+    if r >= d {
+        q1 += 1;
+    }
+    q1
+}
+
+/// Subtracts `q * d` from `u` in place, where `u` has one more limb than `d`.
+/// Returns `true` if the subtraction borrowed, i.e. `q` was too large.
+#[inline]
+fn submul(u: &mut [u64], d: &[u64], q: u64) -> bool {
+    let mut carry = 0_u64;
+    let mut borrow = false;
+    for (u, &d) in u.iter_mut().zip(d) {
+        let p = q as u128 * d as u128 + carry as u128;
+        carry = (p >> 64) as u64;
+        let (t, b1) = u.overflowing_sub(p as u64);
+        let (t, b2) = t.overflowing_sub(borrow as u64);
+        *u = t;
+        borrow = b1 | b2;
+    }
+    let last = &mut u[d.len()];
+    let (t, b1) = last.overflowing_sub(carry);
+    let (t, b2) = t.overflowing_sub(borrow as u64);
+    *last = t;
+    b1 | b2
+}
+
+/// Adds `d` back to `u` in place after a [`submul`] that borrowed, ignoring
+/// the final carry which cancels out the borrow.
+#[inline]
+fn add_back(u: &mut [u64], d: &[u64]) {
+    let mut carry = false;
+    for (u, &d) in u.iter_mut().zip(d) {
+        let (t, c1) = u.overflowing_add(d);
+        let (t, c2) = t.overflowing_add(carry as u64);
+        *u = t;
+        carry = c1 | c2;
+    }
+    let last = &mut u[d.len()];
+    *last = last.wrapping_add(carry as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator for reproducible test values.
+    fn values() -> impl Iterator<Item = U256> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..2000).map(move |i| {
+            let x = U256::from_limbs([next(), next(), next(), next()]);
+            // Vary the magnitude and bit patterns so that all limb counts and
+            // normalisation shifts are covered.
+            match i % 4 {
+                0 => x,
+                1 => x >> (next() % 256) as u32,
+                2 => !(x >> (next() % 256) as u32),
+                _ => (x >> (next() % 256) as u32) | (U256::ONE << (next() % 256) as u32),
+            }
+        })
+    }
+
+    #[test]
+    fn reciprocals() {
+        for d in [1 << 63, (1 << 63) + 1, u64::MAX - 1, u64::MAX] {
+            let v = reciprocal_2by1(d);
+            assert_eq!(
+                v as u128 + (1 << 64),
+                u128::MAX / d as u128,
+                "reciprocal of {d:#x}"
+            );
+        }
+        for (d1, d0) in [(1 << 63, 0), (1 << 63, u64::MAX), (u64::MAX, u64::MAX)] {
+            let d = U256::from_words(0, (d1 as u128) << 64 | d0 as u128);
+            let expected = (U256::MAX >> 64) / d - (U256::ONE << 64);
+            assert_eq!(U256::new(reciprocal_3by2(d1, d0) as _), expected);
+        }
+    }
+
+    #[test]
+    fn div_rem() {
+        let edge = [
+            U256::ONE,
+            U256::new(2),
+            U256::new(10),
+            U256::new(u64::MAX as _),
+            U256::new(u64::MAX as u128 + 1),
+            U256::new(u128::MAX),
+            U256::from_words(1, 0),
+            U256::MAX >> 1,
+            U256::MAX - 1,
+            U256::MAX,
+        ];
+        for d in values().take(500).filter(|d| *d != 0).chain(edge) {
+            let divisor = d.divisor();
+            assert_eq!(divisor.get(), d);
+            for x in values()
+                .take(100)
+                .chain(edge)
+                .chain([d - 1, d, d.wrapping_add(U256::ONE)])
+            {
+                assert_eq!(divisor.div_rem(x), (x / d, x % d), "{x:#x} / {d:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn ops() {
+        let d = Divisor256::from(U256::new(7));
+        assert_eq!(U256::new(50) / d, 7);
+        assert_eq!(U256::new(50) % d, 1);
+        let d = &d;
+        assert_eq!(U256::MAX / d, U256::MAX / 7);
+        assert_eq!(U256::MAX % d, U256::MAX % 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero() {
+        Divisor256::new(U256::ZERO);
+    }
+}
@@ -1,53 +1,346 @@
-//! Module with hacks for creating error variants for standard library errors
-//! without public interfaces.
+//! Module with crate error types, as well as conversions into the
+//! corresponding standard library error types.
 
+use crate::{I256, U256};
 use core::{
-    mem,
-    num::{IntErrorKind, ParseIntError, TryFromIntError},
+    fmt::{self, Display, Formatter},
+    num::{self, IntErrorKind, ParseIntError},
 };
 
-/// Returns a `ParseIntError` from an `IntErrorKind`.
-pub const fn pie(kind: IntErrorKind) -> ParseIntError {
-    unsafe { mem::transmute(kind) }
+/// An error which can be returned when strictly parsing an Ethereum JSON-RPC
+/// `QUANTITY` string.
+///
+/// Each variant corresponds to a rule from the JSON-RPC specification that
+/// the input violated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuantityError {
+    /// The input string is empty.
+    Empty,
+    /// The input string does not start with the lower-case `0x` prefix.
+    MissingPrefix,
+    /// The input string has a `0x` prefix but no digits.
+    EmptyNumber,
+    /// The number has extraneous leading zero digits.
+    LeadingZero,
+    /// The input contains a character that is not a hexadecimal digit.
+    InvalidDigit,
+    /// The number is larger than 256 bits.
+    Overflow,
+}
+
+impl Display for QuantityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "empty hex string",
+            Self::MissingPrefix => "hex string without 0x prefix",
+            Self::EmptyNumber => "hex string \"0x\"",
+            Self::LeadingZero => "hex number with leading zero digits",
+            Self::InvalidDigit => "invalid hex string",
+            Self::Overflow => "hex number > 256 bits",
+        })
+    }
+}
+
+impl core::error::Error for QuantityError {}
+
+/// An error which can be returned when parsing an integer.
+///
+/// In addition to the kind of error, which matches the standard library's
+/// [`ParseIntError::kind`], this records the byte offset into the input string
+/// at which parsing failed, as well as the radix that was used. It is returned
+/// by the `*_detailed` parsing methods, and converts into a [`ParseIntError`]
+/// for use with the standard parsing APIs.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::U256;
+/// use core::num::IntErrorKind;
+///
+/// let err = U256::from_str_radix_detailed("12z4", 16).unwrap_err();
+/// assert_eq!(err.kind(), &IntErrorKind::InvalidDigit);
+/// assert_eq!(err.offset(), 2);
+/// assert_eq!(err.radix(), 16);
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid digit found in string at byte 2 for radix 16",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    kind: IntErrorKind,
+    offset: usize,
+    radix: u32,
+}
+
+impl ParseError {
+    /// Creates a new parse error.
+    pub(crate) const fn new(kind: IntErrorKind, offset: usize, radix: u32) -> Self {
+        Self {
+            kind,
+            offset,
+            radix,
+        }
+    }
+
+    /// Returns the detailed cause of parsing an integer failing.
+    pub const fn kind(&self) -> &IntErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset into the input string at which parsing failed.
+    ///
+    /// For invalid digits, this is the offset of the first invalid digit. For
+    /// overflows, this is the offset of the first digit that caused the
+    /// integer to no longer fit. For empty numbers, this is the length of the
+    /// input string.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the radix that was used for parsing.
+    pub const fn radix(&self) -> u32 {
+        self.radix
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind {
+            IntErrorKind::Empty => f.write_str("cannot parse integer from empty string"),
+            IntErrorKind::PosOverflow => write!(
+                f,
+                "number too large to fit in target type at byte {}",
+                self.offset,
+            ),
+            IntErrorKind::NegOverflow => write!(
+                f,
+                "number too small to fit in target type at byte {}",
+                self.offset,
+            ),
+            _ => write!(
+                f,
+                "invalid digit found in string at byte {} for radix {}",
+                self.offset, self.radix,
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+impl From<ParseError> for ParseIntError {
+    fn from(err: ParseError) -> Self {
+        // There is no public constructor for `ParseIntError`, so produce one
+        // of the matching kind by parsing a primitive integer.
+        let result = match err.kind {
+            IntErrorKind::Empty => "".parse::<u8>(),
+            IntErrorKind::PosOverflow => "256".parse::<u8>(),
+            IntErrorKind::NegOverflow => "-129".parse::<i8>().map(|_| 0),
+            _ => "?".parse::<u8>(),
+        };
+        result.unwrap_err()
+    }
+}
+
+/// The error type returned when a checked integer conversion fails.
+///
+/// Unlike the standard library's [`TryFromIntError`], this indicates whether
+/// the source value was too large or too small for the target type. It is
+/// returned by the `try_*_detailed` conversion methods, and converts into a
+/// [`TryFromIntError`] for use with the standard conversion traits.
+///
+/// [`TryFromIntError`]: num::TryFromIntError
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{I256, U256};
+/// use core::num::IntErrorKind;
+///
+/// let err = U256::try_from_detailed(I256::MINUS_ONE).unwrap_err();
+/// assert_eq!(err.kind(), &IntErrorKind::NegOverflow);
+/// let err = U256::MAX.try_into_detailed::<u8>().unwrap_err();
+/// assert_eq!(err.kind(), &IntErrorKind::PosOverflow);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TryFromIntError {
+    negative: bool,
+}
+
+impl TryFromIntError {
+    /// Returns an error for a source value that is too large for the target.
+    pub(crate) const fn pos_overflow() -> Self {
+        Self { negative: false }
+    }
+
+    /// Returns an error for a source value that is too small for the target.
+    pub(crate) const fn neg_overflow() -> Self {
+        Self { negative: true }
+    }
+
+    /// Returns the detailed cause of the conversion failing. This is either
+    /// [`IntErrorKind::PosOverflow`] if the source value was too large, or
+    /// [`IntErrorKind::NegOverflow`] if it was too small.
+    pub const fn kind(&self) -> &IntErrorKind {
+        if self.negative {
+            &IntErrorKind::NegOverflow
+        } else {
+            &IntErrorKind::PosOverflow
+        }
+    }
+}
+
+impl Display for TryFromIntError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(if self.negative {
+            "out of range integral type conversion attempted: value too small"
+        } else {
+            "out of range integral type conversion attempted: value too large"
+        })
+    }
+}
+
+impl core::error::Error for TryFromIntError {}
+
+impl From<TryFromIntError> for num::TryFromIntError {
+    fn from(_: TryFromIntError) -> Self {
+        tfie()
+    }
+}
+
+/// An integer type that can be converted with `try_from_detailed`.
+///
+/// This trait is sealed: it is public so it can appear in method bounds, but
+/// it is not exported and cannot be named or implemented outside of this
+/// crate.
+pub trait Integer: Copy {
+    /// Returns `true` if the value is negative, and a failed conversion is
+    /// therefore a negative overflow.
+    fn is_negative(self) -> bool;
+}
+
+macro_rules! impl_integer {
+    (signed: $($t:ty),*) => {$(
+        impl Integer for $t {
+            fn is_negative(self) -> bool {
+                self < 0
+            }
+        }
+    )*};
+    (unsigned: $($t:ty),*) => {$(
+        impl Integer for $t {
+            fn is_negative(self) -> bool {
+                false
+            }
+        }
+    )*};
+}
+
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize, U256);
+
+impl Integer for I256 {
+    fn is_negative(self) -> bool {
+        I256::is_negative(self)
+    }
 }
 
 /// Returns a `TryFromIntError`.
-pub const fn tfie() -> TryFromIntError {
-    unsafe { mem::transmute(()) }
+pub(crate) fn tfie() -> num::TryFromIntError {
+    // There is no public constructor for `TryFromIntError`, so produce one from
+    // a failed primitive integer conversion.
+    u8::try_from(-1_i8).unwrap_err()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::num::NonZeroU32;
+    use alloc::string::ToString as _;
 
     #[test]
-    #[allow(clippy::from_str_radix_10)]
     fn parse_int_error() {
+        for kind in [
+            IntErrorKind::Empty,
+            IntErrorKind::InvalidDigit,
+            IntErrorKind::PosOverflow,
+            IntErrorKind::NegOverflow,
+        ] {
+            let err = ParseIntError::from(ParseError::new(kind, 0, 10));
+            assert_eq!(err.kind(), &kind);
+        }
+    }
+
+    #[test]
+    fn parse_error_display() {
+        let err = |kind| ParseError::new(kind, 3, 16).to_string();
         assert_eq!(
-            pie(IntErrorKind::Empty),
-            u8::from_str_radix("", 2).unwrap_err(),
+            err(IntErrorKind::Empty),
+            "cannot parse integer from empty string",
         );
         assert_eq!(
-            pie(IntErrorKind::InvalidDigit),
-            u8::from_str_radix("?", 2).unwrap_err(),
+            err(IntErrorKind::InvalidDigit),
+            "invalid digit found in string at byte 3 for radix 16",
         );
         assert_eq!(
-            pie(IntErrorKind::PosOverflow),
-            u8::from_str_radix("zzz", 36).unwrap_err(),
+            err(IntErrorKind::PosOverflow),
+            "number too large to fit in target type at byte 3",
+        );
+    }
+
+    #[test]
+    fn try_from_int_error() {
+        assert_eq!(
+            num::TryFromIntError::from(TryFromIntError::neg_overflow()),
+            u8::try_from(-1).unwrap_err(),
         );
         assert_eq!(
-            pie(IntErrorKind::NegOverflow),
-            i8::from_str_radix("-1337", 10).unwrap_err(),
+            TryFromIntError::pos_overflow().kind(),
+            &IntErrorKind::PosOverflow,
         );
         assert_eq!(
-            pie(IntErrorKind::Zero),
-            "0".parse::<NonZeroU32>().unwrap_err(),
+            TryFromIntError::neg_overflow().kind(),
+            &IntErrorKind::NegOverflow,
         );
     }
 
     #[test]
-    fn try_from_int_error() {
-        assert_eq!(tfie(), u8::try_from(-1).unwrap_err());
+    fn try_from_detailed() {
+        let neg = Err(TryFromIntError::neg_overflow());
+        let pos = Err(TryFromIntError::pos_overflow());
+        assert_eq!(U256::try_from_detailed(-1_i8), neg);
+        assert_eq!(U256::try_from_detailed(isize::MIN), neg);
+        assert_eq!(
+            U256::try_from_detailed(i128::MAX),
+            Ok(U256::new(i128::MAX as _))
+        );
+        assert_eq!(U256::try_from_detailed(I256::MIN), neg);
+        assert_eq!(U256::try_from_detailed(I256::MAX), Ok(I256::MAX.as_u256()));
+        assert_eq!(
+            I256::try_from_detailed(u128::MAX),
+            Ok(U256::new(u128::MAX).as_i256()),
+        );
+        assert_eq!(I256::try_from_detailed(U256::MAX), pos);
+        assert_eq!(I256::try_from_detailed(U256::ZERO), Ok(I256::ZERO));
+        assert_eq!(I256::try_from_detailed(-1_i64), Ok(I256::MINUS_ONE));
+    }
+
+    #[test]
+    fn std_error_compatibility() {
+        use alloc::boxed::Box;
+
+        fn parse(s: &str) -> Result<u64, Box<dyn core::error::Error>> {
+            let x: U256 = s.parse()?;
+            let y = U256::from_str_radix_detailed(s, 10)?;
+            let z = I256::try_from_detailed(x)?;
+            Ok(u64::try_from(x + y)? + z.try_into_detailed::<u64>()?)
+        }
+
+        assert_eq!(parse("21").unwrap(), 63);
+        assert!(parse("0x1").is_err());
+        assert_eq!(
+            parse("99999999999999999999").unwrap_err().to_string(),
+            "out of range integral type conversion attempted",
+        );
     }
 }
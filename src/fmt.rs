@@ -6,38 +6,88 @@
 //! <https://doc.rust-lang.org/src/core/num/mod.rs.html>
 //! <https://doc.rust-lang.org/src/core/fmt/num.rs.html>
 
-use crate::uint::U256;
-use core::{
-    fmt,
-    mem::{self, MaybeUninit},
-    num::{IntErrorKind, ParseIntError},
-    ops::{Add, Mul, Sub},
-    ptr, slice, str,
+use crate::{
+    error::{ParseError, QuantityError},
+    uint::U256,
 };
+#[cfg(not(feature = "safe"))]
+use core::ptr;
+use core::{fmt, num::IntErrorKind, str};
 
 #[doc(hidden)]
-pub(crate) trait FromStrRadixHelper:
-    PartialOrd + Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
-{
+pub(crate) trait FromStrRadixHelper: PartialOrd + Copy {
     const MIN: Self;
     fn from_u32(u: u32) -> Self;
+    fn from_u64(u: u64) -> Self;
+    fn checked_neg(&self) -> Option<Self>;
     fn checked_mul(&self, other: u32) -> Option<Self>;
     fn checked_sub(&self, other: u32) -> Option<Self>;
     fn checked_add(&self, other: u32) -> Option<Self>;
+    fn checked_mul_add(&self, mul: u64, add: u64) -> Option<Self>;
+    fn checked_mul_sub(&self, mul: u64, sub: u64) -> Option<Self>;
 }
 
-#[inline(always)]
-fn can_not_overflow<T>(radix: u32, is_signed_ty: bool, digits: &[u8]) -> bool {
-    radix <= 16 && digits.len() <= mem::size_of::<T>() * 2 - is_signed_ty as usize
+/// Lookup table from radixes to the maximum number of digits that always fit
+/// in a `u64`, such that the radix raised to that power does too.
+const CHUNK_LEN: [u8; 37] = {
+    let mut lut = [0; 37];
+    let mut radix = 2;
+    while radix < 37 {
+        let mut pow = radix as u64;
+        while let Some(next) = pow.checked_mul(radix as u64) {
+            pow = next;
+            lut[radix] += 1;
+        }
+        lut[radix] += 1;
+        radix += 1;
+    }
+    lut
+};
+
+/// Lookup table from ASCII characters to their digit values, with invalid
+/// characters mapping to `u8::MAX`.
+const DIGIT_LUT: [u8; 256] = {
+    let mut lut = [u8::MAX; 256];
+    let mut c = 0;
+    while c < 256 {
+        lut[c] = match (c as u8 as char).to_digit(36) {
+            Some(x) => x as u8,
+            None => u8::MAX,
+        };
+        c += 1;
+    }
+    lut
+};
+
+/// Parses a chunk of at most [`CHUNK_LEN`] digits.
+#[inline]
+fn parse_chunk(chunk: &[u8], radix: u32) -> Option<u64> {
+    let mut value = 0_u64;
+    for &c in chunk {
+        let x = DIGIT_LUT[c as usize] as u32;
+        if x >= radix {
+            return None;
+        }
+        value = value * radix as u64 + x as u64;
+    }
+    Some(value)
 }
 
+#[inline]
 pub(crate) fn from_str_radix<T: FromStrRadixHelper>(
     src: &str,
     radix: u32,
     prefix: Option<&str>,
-) -> Result<T, ParseIntError> {
+) -> Result<T, ParseError> {
+    from_ascii_radix(src.as_bytes(), radix, prefix.map(str::as_bytes))
+}
+
+pub(crate) fn from_ascii_radix<T: FromStrRadixHelper>(
+    src: &[u8],
+    radix: u32,
+    prefix: Option<&[u8]>,
+) -> Result<T, ParseError> {
     use self::IntErrorKind::*;
-    use crate::error::pie;
 
     assert!(
         (2..=36).contains(&radix),
@@ -45,21 +95,21 @@ pub(crate) fn from_str_radix<T: FromStrRadixHelper>(
         radix
     );
 
+    let err = |kind, rest: &[u8]| ParseError::new(kind, src.len() - rest.len(), radix);
+
     if src.is_empty() {
-        return Err(pie(Empty));
+        return Err(err(Empty, &[]));
     }
 
     let is_signed_ty = T::from_u32(0) > T::MIN;
 
-    // all valid digits are ascii, so we will just iterate over the utf8 bytes
-    // and cast them to chars. .to_digit() will safely return None for anything
+    // all valid digits are ascii, so we will just iterate over the bytes and
+    // cast them to chars. .to_digit() will safely return None for anything
     // other than a valid ascii digit for the given radix, including the first-byte
     // of multi-byte sequences
-    let src = src.as_bytes();
-
     let (is_positive, prefixed_digits) = match src[0] {
         b'+' | b'-' if src[1..].is_empty() => {
-            return Err(pie(InvalidDigit));
+            return Err(err(InvalidDigit, &[]));
         }
         b'+' => (true, &src[1..]),
         b'-' if is_signed_ty => (false, &src[1..]),
@@ -68,103 +118,290 @@ pub(crate) fn from_str_radix<T: FromStrRadixHelper>(
 
     let digits = match prefix {
         Some(prefix) => prefixed_digits
-            .strip_prefix(prefix.as_bytes())
-            .ok_or(pie(InvalidDigit))?,
+            .strip_prefix(prefix)
+            .ok_or_else(|| err(InvalidDigit, prefixed_digits))?,
         None => prefixed_digits,
     };
 
+    // If all the digits fit in a single chunk, then we can be certain that an
+    // overflow will not occur, since any `u64` value fits in the types that we
+    // are parsing into.
+    let chunk_len = CHUNK_LEN[radix as usize] as usize;
+    if digits.len() <= chunk_len {
+        let value = parse_chunk(digits, radix).map(T::from_u64);
+        let value = if is_positive {
+            value
+        } else {
+            value.and_then(|value| value.checked_neg())
+        };
+        if let Some(value) = value {
+            return Ok(value);
+        }
+    }
+
     let mut result = T::from_u32(0);
 
-    if can_not_overflow::<T>(radix, is_signed_ty, digits) {
-        // If the len of the str is short compared to the range of the type
-        // we are parsing into, then we can be certain that an overflow will not occur.
-        // This bound is when `radix.pow(digits.len()) - 1 <= T::MAX` but the condition
-        // above is a faster (conservative) approximation of this.
-        //
-        // Consider radix 16 as it has the highest information density per digit and will thus overflow the earliest:
-        // `u8::MAX` is `ff` - any str of len 2 is guaranteed to not overflow.
-        // `i8::MAX` is `7f` - only a str of len 1 is guaranteed to not overflow.
-        macro_rules! run_unchecked_loop {
-            ($unchecked_additive_op:expr) => {
-                for &c in digits {
-                    result = result * T::from_u32(radix);
-                    let x = (c as char).to_digit(radix).ok_or(pie(InvalidDigit))?;
-                    result = $unchecked_additive_op(result, T::from_u32(x));
-                }
-            };
+    // Accumulate as many digits as fit in a `u64` at a time, and fold them
+    // into the result with a single multiply-add. This is significantly faster
+    // than doing 256-bit arithmetic for every digit.
+    let mut digits = digits;
+    for chunk in digits.chunks(chunk_len) {
+        let next = parse_chunk(chunk, radix).and_then(|value| {
+            let mul = (radix as u64).pow(chunk.len() as _);
+            if is_positive {
+                result.checked_mul_add(mul, value)
+            } else {
+                result.checked_mul_sub(mul, value)
+            }
+        });
+        match next {
+            Some(next) => result = next,
+            None => break,
         }
-        if is_positive {
-            run_unchecked_loop!(<T as core::ops::Add>::add)
-        } else {
-            run_unchecked_loop!(<T as core::ops::Sub>::sub)
+        digits = &digits[chunk.len()..];
+    }
+
+    // If a chunk failed to parse, then it either contains an invalid digit or
+    // overflows. Parse the remaining digits one at a time in order to report
+    // the exact error.
+    macro_rules! run_checked_loop {
+        ($checked_additive_op:ident, $overflow_kind:expr) => {
+            for (i, &c) in digits.iter().enumerate() {
+                // When `radix` is passed in as a literal, rather than doing a slow `imul`
+                // the compiler can use shifts if `radix` can be expressed as a
+                // sum of powers of 2 (x*10 can be written as x*8 + x*2).
+                // When the compiler can't use these optimisations,
+                // the latency of the multiplication can be hidden by issuing it
+                // before the result is needed to improve performance on
+                // modern out-of-order CPU as multiplication here is slower
+                // than the other instructions, we can get the end result faster
+                // doing multiplication first and let the CPU spends other cycles
+                // doing other computation and get multiplication result later.
+                let mul = result.checked_mul(radix);
+                let x = (c as char)
+                    .to_digit(radix)
+                    .ok_or_else(|| err(InvalidDigit, &digits[i..]))?;
+                let overflow = || err($overflow_kind, &digits[i..]);
+                result = mul.ok_or_else(overflow)?;
+                result = T::$checked_additive_op(&result, x).ok_or_else(overflow)?;
+            }
         };
+    }
+    if is_positive {
+        run_checked_loop!(checked_add, PosOverflow)
     } else {
-        macro_rules! run_checked_loop {
-            ($checked_additive_op:ident, $overflow_err:expr) => {
-                for &c in digits {
-                    // When `radix` is passed in as a literal, rather than doing a slow `imul`
-                    // the compiler can use shifts if `radix` can be expressed as a
-                    // sum of powers of 2 (x*10 can be written as x*8 + x*2).
-                    // When the compiler can't use these optimisations,
-                    // the latency of the multiplication can be hidden by issuing it
-                    // before the result is needed to improve performance on
-                    // modern out-of-order CPU as multiplication here is slower
-                    // than the other instructions, we can get the end result faster
-                    // doing multiplication first and let the CPU spends other cycles
-                    // doing other computation and get multiplication result later.
-                    let mul = result.checked_mul(radix);
-                    let x = (c as char).to_digit(radix).ok_or(pie(InvalidDigit))?;
-                    result = mul.ok_or_else($overflow_err)?;
-                    result = T::$checked_additive_op(&result, x).ok_or_else($overflow_err)?;
-                }
-            };
-        }
+        run_checked_loop!(checked_sub, NegOverflow)
+    };
+    Ok(result)
+}
+
+pub(crate) fn from_str_prefixed<T: FromStrRadixHelper>(src: &str) -> Result<T, ParseError> {
+    from_str_radix(src, 16, Some("0x")).or_else(|_| from_str_radix(src, 10, None))
+}
+
+pub(crate) fn from_str_lenient<T: FromStrRadixHelper>(src: &str) -> Result<T, ParseError> {
+    use self::IntErrorKind::*;
+
+    let err = |kind, radix, rest: &[u8]| ParseError::new(kind, src.len() - rest.len(), radix);
+
+    if src.is_empty() {
+        return Err(err(Empty, 10, &[]));
+    }
+
+    let is_signed_ty = T::from_u32(0) > T::MIN;
+    let (is_positive, unsigned) = match src.as_bytes() {
+        [b'+', rest @ ..] => (true, rest),
+        [b'-', rest @ ..] if is_signed_ty => (false, rest),
+        src => (true, src),
+    };
+    let overflow = if is_positive {
+        PosOverflow
+    } else {
+        NegOverflow
+    };
+    let (radix, digits) = match unsigned {
+        [b'0', b'b' | b'B', rest @ ..] => (2, rest),
+        [b'0', b'o' | b'O', rest @ ..] => (8, rest),
+        [b'0', b'x' | b'X', rest @ ..] => (16, rest),
+        _ => (10, unsigned),
+    };
+
+    // Split the digits into integer, fractional and exponent parts. Note that
+    // scientific notation is only supported for decimal numbers, and that we
+    // only ever slice the end of `digits` so that error offsets can be
+    // computed from the remaining length.
+    let exponent = digits
+        .iter()
+        .position(|c| matches!(c, b'e' | b'E'))
+        .filter(|_| radix == 10);
+    let mantissa_end = exponent.unwrap_or(digits.len());
+    let point = digits[..mantissa_end]
+        .iter()
+        .position(|&c| c == b'.')
+        .filter(|_| exponent.is_some());
+    let int_end = point.unwrap_or(mantissa_end);
+
+    let digit = |i: usize| {
+        (digits[i] as char)
+            .to_digit(radix)
+            .ok_or_else(|| err(InvalidDigit, radix, &digits[i..]))
+    };
+    let accumulate = |result: T, x: u32, i: usize| {
+        let result = result.checked_mul(radix);
         if is_positive {
-            run_checked_loop!(checked_add, || pie(PosOverflow))
+            result.and_then(|r| r.checked_add(x))
         } else {
-            run_checked_loop!(checked_sub, || pie(NegOverflow))
+            result.and_then(|r| r.checked_sub(x))
+        }
+        .ok_or_else(|| err(overflow, radix, &digits[i..]))
+    };
+
+    let mut exp = 0_u32;
+    let mut exp_negative = false;
+    if let Some(start) = exponent {
+        let mut any = false;
+        let start = match digits.get(start + 1) {
+            Some(b'+') => start + 2,
+            Some(b'-') => {
+                exp_negative = true;
+                start + 2
+            }
+            _ => start + 1,
         };
+        for (i, &c) in digits.iter().enumerate().skip(start) {
+            if c == b'_' && any {
+                continue;
+            }
+            let x = digit(i)?;
+            // A huge negative exponent just drops every digit, so saturate
+            // instead of reporting an overflow.
+            exp = exp
+                .checked_mul(10)
+                .and_then(|e| e.checked_add(x))
+                .or_else(|| exp_negative.then_some(u32::MAX))
+                .ok_or_else(|| err(overflow, 10, &digits[i..]))?;
+            any = true;
+        }
+        if !any {
+            return Err(err(InvalidDigit, 10, &[]));
+        }
+    }
+
+    // A negative exponent drops trailing integer digits (and the whole
+    // fractional part), which is only allowed when they are all zero.
+    let mut keep = usize::MAX;
+    if exp_negative {
+        let int_digits = digits[..int_end].iter().filter(|&&c| c != b'_').count();
+        keep = int_digits.saturating_sub(exp as usize);
+        exp = 0;
+    }
+
+    // Like with Rust integer literals, `_` separators are allowed anywhere
+    // after the first digit or a radix prefix.
+    let mut result = T::from_u32(0);
+    let mut any = false;
+    for (i, &c) in digits.iter().enumerate().take(int_end) {
+        if c == b'_' && (any || radix != 10) {
+            continue;
+        }
+        let x = digit(i)?;
+        if keep > 0 {
+            result = accumulate(result, x, i)?;
+            keep -= 1;
+        } else if x != 0 {
+            return Err(err(InvalidDigit, radix, &digits[i..]));
+        }
+        any = true;
+    }
+    if !any {
+        return Err(err(InvalidDigit, radix, &digits[int_end..]));
+    }
+
+    // Fractional digits shift the exponent, and are only allowed as long as
+    // the resulting number is an integer.
+    if let Some(point) = point {
+        let mut any = false;
+        for (i, &c) in digits.iter().enumerate().take(mantissa_end).skip(point + 1) {
+            if c == b'_' && any {
+                continue;
+            }
+            let x = digit(i)?;
+            if exp > 0 {
+                result = accumulate(result, x, i)?;
+                exp -= 1;
+            } else if x != 0 {
+                return Err(err(InvalidDigit, radix, &digits[i..]));
+            }
+            any = true;
+        }
+        if !any {
+            return Err(err(InvalidDigit, radix, &digits[mantissa_end..]));
+        }
+    }
+
+    if result != T::from_u32(0) {
+        for _ in 0..exp {
+            result = accumulate(result, 0, mantissa_end)?;
+        }
     }
     Ok(result)
 }
 
-pub(crate) fn from_str_prefixed<T: FromStrRadixHelper>(src: &str) -> Result<T, ParseIntError> {
-    from_str_radix(src, 16, Some("0x")).or_else(|_| from_str_radix(src, 10, None))
+pub(crate) fn from_quantity_str(src: &str) -> Result<U256, QuantityError> {
+    if src.is_empty() {
+        return Err(QuantityError::Empty);
+    }
+    let digits = src.strip_prefix("0x").ok_or(QuantityError::MissingPrefix)?;
+    match digits.as_bytes() {
+        [] => return Err(QuantityError::EmptyNumber),
+        [b'0', _, ..] => return Err(QuantityError::LeadingZero),
+        _ => {}
+    }
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(QuantityError::InvalidDigit);
+    }
+    if digits.len() > 64 {
+        return Err(QuantityError::Overflow);
+    }
+    from_str_radix(digits, 16, None).map_err(|_| QuantityError::InvalidDigit)
 }
 
 pub(crate) trait GenericRadix: Sized {
     const BASE: u8;
     const PREFIX: &'static str;
     fn digit(x: u8) -> u8;
-    fn fmt_u256(&self, mut x: U256, is_nonnegative: bool, f: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt_u256(&self, x: U256, is_nonnegative: bool, f: &mut fmt::Formatter) -> fmt::Result {
         // The radix can be as low as 2, so we need a buffer of at least 256
         // characters for a base 2 number.
-        let zero = U256::ZERO;
-        let mut buf = [MaybeUninit::<u8>::uninit(); 256];
+        let mut buf = [0_u8; 256];
         let mut curr = buf.len();
-        let base = U256::from(Self::BASE);
+        // All supported radixes are powers of two, so digits can be extracted
+        // with masks and shifts instead of divisions.
+        debug_assert!(Self::BASE.is_power_of_two());
+        let shift = Self::BASE.trailing_zeros();
+        let mask = Self::BASE - 1;
         // Accumulate each digit of the number from the least significant
-        // to the most significant figure.
-        for byte in buf.iter_mut().rev() {
-            let n = x % base; // Get the current place value.
-            x /= base; // Deaccumulate the number.
-            byte.write(Self::digit(n.as_u8())); // Store the digit in the buffer.
+        // to the most significant figure, switching to cheaper 128-bit
+        // arithmetic as soon as the number fits.
+        let mut x = x;
+        while *x.high() != 0 {
+            curr -= 1;
+            buf[curr] = Self::digit(x.as_u8() & mask);
+            x >>= shift;
+        }
+        let mut x = *x.low();
+        loop {
             curr -= 1;
-            if x == zero {
+            buf[curr] = Self::digit(x as u8 & mask);
+            x >>= shift;
+            if x == 0 {
                 // No more digits left to accumulate.
                 break;
-            };
+            }
         }
-        let buf = &buf[curr..];
-        // SAFETY: The only chars in `buf` are created by `Self::digit` which are assumed to be
-        // valid UTF-8
-        let buf = unsafe {
-            str::from_utf8_unchecked(slice::from_raw_parts(
-                &buf[0] as *const _ as *const u8,
-                buf.len(),
-            ))
-        };
-        f.pad_integral(is_nonnegative, Self::PREFIX, buf)
+        // The only chars in `buf` are created by `Self::digit` which are
+        // assumed to be valid UTF-8.
+        f.pad_integral(is_nonnegative, Self::PREFIX, ascii_str(&buf[curr..]))
     }
 }
 
@@ -211,74 +448,189 @@ const DEC_DIGITS_LUT: &[u8; 200] = b"\
     6061626364656667686970717273747576777879\
     8081828384858687888990919293949596979899";
 
-pub(crate) fn fmt_u256(mut n: U256, is_nonnegative: bool, f: &mut fmt::Formatter) -> fmt::Result {
+/// The largest power of 10 that fits in a `u64`.
+const TEN19: u64 = 10_000_000_000_000_000_000;
+
+pub(crate) fn fmt_u256(n: U256, is_nonnegative: bool, f: &mut fmt::Formatter) -> fmt::Result {
     // 2^256 is about 1*10^78, so 79 gives an extra byte of space
-    let mut buf = [MaybeUninit::<u8>::uninit(); 79];
-    let mut curr = buf.len() as isize;
-    let buf_ptr = &mut buf[0] as *mut _ as *mut u8;
-    let lut_ptr = DEC_DIGITS_LUT.as_ptr();
-
-    // SAFETY: Since `d1` and `d2` are always less than or equal to `198`, we
-    // can copy from `lut_ptr[d1..d1 + 1]` and `lut_ptr[d2..d2 + 1]`. To show
-    // that it's OK to copy into `buf_ptr`, notice that at the beginning
-    // `curr == buf.len() == 39 > log(n)` since `n < 2^128 < 10^39`, and at
-    // each step this is kept the same as `n` is divided. Since `n` is always
-    // non-negative, this means that `curr > 0` so `buf_ptr[curr..curr + 1]`
-    // is safe to access.
-    unsafe {
-        // eagerly decode 4 characters at a time
-        while n >= 10000 {
-            let rem = (n % 10000).as_isize();
-            n /= 10000;
-
-            let d1 = (rem / 100) << 1;
-            let d2 = (rem % 100) << 1;
-            curr -= 4;
-
-            // We are allowed to copy to `buf_ptr[curr..curr + 3]` here since
-            // otherwise `curr < 0`. But then `n` was originally at least `10000^10`
-            // which is `10^40 > 2^128 > n`.
-            ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-            ptr::copy_nonoverlapping(lut_ptr.offset(d2), buf_ptr.offset(curr + 2), 2);
-        }
+    let mut buf = [0_u8; 79];
+    let curr = fmt_dec(n, &mut buf);
 
-        // if we reach here numbers are <= 9999, so at most 4 chars long
-        let mut n = n.as_isize(); // possibly reduce 64bit math
+    // All the chars are valid UTF-8 since `DEC_DIGITS_LUT` is.
+    f.pad_integral(is_nonnegative, "", ascii_str(&buf[curr..]))
+}
 
-        // decode 2 more chars, if > 2 chars
-        if n >= 100 {
-            let d1 = (n % 100) << 1;
-            n /= 100;
-            curr -= 2;
-            ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-        }
+/// Formats a 256-bit integer in decimal into the end of `buf`, returning the
+/// index of the first written character.
+fn fmt_dec(mut n: U256, buf: &mut [u8]) -> usize {
+    let mut curr = buf.len();
+
+    // Split off chunks of 19 decimal digits with a single 256-by-64-bit
+    // division each, until the remaining number fits in a `u64`. Every chunk
+    // except for the most significant one is zero-padded to its full width.
+    while *n.high() != 0 || *n.low() > u64::MAX as u128 {
+        let (q, r) = n.div_rem_u64(TEN19);
+        n = q;
+        let start = fmt_u64(r, &mut buf[..curr]);
+        buf[curr - 19..start].fill(b'0');
+        curr -= 19;
+    }
+    fmt_u64(n.as_u64(), &mut buf[..curr])
+}
 
-        // decode last 1 or 2 chars
-        if n < 10 {
-            curr -= 1;
-            *buf_ptr.offset(curr) = (n as u8) + b'0';
-        } else {
-            let d1 = n << 1;
-            curr -= 2;
-            ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+/// Formats a 256-bit integer as a zero-padded decimal number.
+pub(crate) fn to_dec_array_padded(n: U256) -> [u8; 78] {
+    let mut buf = [b'0'; 78];
+    fmt_dec(n, &mut buf);
+    buf
+}
+
+/// Formats a 256-bit integer as a `0x`-prefixed, zero-padded, lower-case
+/// hexadecimal number.
+pub(crate) fn to_hex_array(n: U256) -> [u8; 66] {
+    let mut buf = [0_u8; 66];
+    buf[..2].copy_from_slice(b"0x");
+    for (digits, byte) in buf[2..].chunks_exact_mut(2).zip(n.to_be_bytes()) {
+        digits[0] = DIGITS[(byte >> 4) as usize];
+        digits[1] = DIGITS[(byte & 0xf) as usize];
+    }
+    buf
+}
+
+/// Digit characters for all supported radixes.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Formats a 256-bit integer in an arbitrary radix into the end of `buf`,
+/// returning the index of the first written character. Digits above 9 are
+/// formatted with lower-case characters.
+///
+/// # Panics
+///
+/// This function panics if `radix` is not in the range from 2 to 36, or if
+/// the buffer is too small to hold all the digits.
+pub(crate) fn fmt_radix(mut n: U256, radix: u32, buf: &mut [u8]) -> usize {
+    assert!(
+        (2..=36).contains(&radix),
+        "to_str_radix: must lie in the range `[2, 36]` - found {}",
+        radix
+    );
+    if radix == 10 {
+        return fmt_dec(n, buf);
+    }
+
+    // Like with decimals, split off chunks of as many digits as fit in a
+    // `u64`, so that all but one division per chunk use native arithmetic.
+    let radix = radix as u64;
+    let (mut len, mut pow) = (1, radix);
+    while let Some(next) = pow.checked_mul(radix) {
+        len += 1;
+        pow = next;
+    }
+
+    let mut curr = buf.len();
+    while *n.high() != 0 || *n.low() > u64::MAX as u128 {
+        let (q, mut r) = n.div_rem_u64(pow);
+        n = q;
+        for byte in buf[curr - len..curr].iter_mut().rev() {
+            *byte = DIGITS[(r % radix) as usize];
+            r /= radix;
         }
+        curr -= len;
     }
+    let mut n = n.as_u64();
+    loop {
+        curr -= 1;
+        buf[curr] = DIGITS[(n % radix) as usize];
+        n /= radix;
+        if n == 0 {
+            break curr;
+        }
+    }
+}
 
-    // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
-    // UTF-8 since `DEC_DIGITS_LUT` is
-    let buf_slice = unsafe {
-        str::from_utf8_unchecked(slice::from_raw_parts(
-            buf_ptr.offset(curr),
-            buf.len() - curr as usize,
-        ))
+/// Formats a `u64` into the end of `buf`, returning the index of the first
+/// written character.
+///
+/// # Panics
+///
+/// This function panics if the buffer is too small to hold all the digits.
+#[inline]
+fn fmt_u64(mut n: u64, buf: &mut [u8]) -> usize {
+    // `u64::MAX` has 20 decimal digits, so any buffer at least that long fits
+    // every value.
+    assert!(buf.len() >= 20 || n < 10_u64.pow(buf.len() as u32));
+    let mut curr = buf.len();
+
+    // Copies the two digits at offset `d` of the lookup table, which is always
+    // of the form `x << 1` for some `x < 100`, to `buf[curr..curr + 2]`.
+    let copy_digits = |buf: &mut [u8], curr: usize, d: usize| {
+        #[cfg(not(feature = "safe"))]
+        // SAFETY: The assertion above guarantees that `buf` has space for all
+        // the digits, so `curr + 2 <= buf.len()`, and `d + 2 <= 200` which is
+        // the length of the lookup table.
+        unsafe {
+            let src = DEC_DIGITS_LUT.as_ptr().add(d);
+            ptr::copy_nonoverlapping(src, buf.as_mut_ptr().add(curr), 2);
+        }
+        #[cfg(feature = "safe")]
+        buf[curr..curr + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
     };
-    f.pad_integral(is_nonnegative, "", buf_slice)
+
+    // eagerly decode 4 characters at a time
+    while n >= 10000 {
+        let rem = (n % 10000) as usize;
+        n /= 10000;
+
+        let d1 = (rem / 100) << 1;
+        let d2 = (rem % 100) << 1;
+        curr -= 4;
+        copy_digits(buf, curr, d1);
+        copy_digits(buf, curr + 2, d2);
+    }
+
+    // if we reach here numbers are <= 9999, so at most 4 chars long
+    let mut n = n as usize;
+
+    // decode 2 more chars, if > 2 chars
+    if n >= 100 {
+        let d1 = (n % 100) << 1;
+        n /= 100;
+        curr -= 2;
+        copy_digits(buf, curr, d1);
+    }
+
+    // decode last 1 or 2 chars
+    if n < 10 {
+        curr -= 1;
+        buf[curr] = (n as u8) + b'0';
+    } else {
+        let d1 = n << 1;
+        curr -= 2;
+        copy_digits(buf, curr, d1);
+    }
+
+    curr
+}
+
+/// Returns a string slice of a buffer of ASCII characters.
+///
+/// Validation is skipped unless the `safe` feature is enabled, so callers must
+/// only ever pass in ASCII bytes.
+#[inline]
+pub(crate) fn ascii_str(buf: &[u8]) -> &str {
+    debug_assert!(buf.is_ascii());
+    #[cfg(not(feature = "safe"))]
+    // SAFETY: ASCII characters are always valid UTF-8.
+    return unsafe { str::from_utf8_unchecked(buf) };
+    #[cfg(feature = "safe")]
+    return str::from_utf8(buf).expect("buffer is ASCII");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::int::I256;
+    use alloc::format;
 
     #[test]
     fn from_str_prefixed() {
@@ -286,6 +638,31 @@ mod tests {
         assert_eq!(from_str_radix::<I256>("-0xf", 16, Some("0x")).unwrap(), -15);
     }
 
+    #[test]
+    fn from_quantity_str() {
+        use super::from_quantity_str as q;
+
+        assert_eq!(q("0x0"), Ok(U256::ZERO));
+        assert_eq!(q("0x400"), Ok(U256::new(1024)));
+        assert_eq!(q("0xFf"), Ok(U256::new(255)));
+        assert_eq!(q(&format!("0x{}", "f".repeat(64))), Ok(U256::MAX));
+
+        assert_eq!(q(""), Err(QuantityError::Empty));
+        assert_eq!(q("400"), Err(QuantityError::MissingPrefix));
+        assert_eq!(q("0X400"), Err(QuantityError::MissingPrefix));
+        assert_eq!(q("+0x400"), Err(QuantityError::MissingPrefix));
+        assert_eq!(q("0x"), Err(QuantityError::EmptyNumber));
+        assert_eq!(q("0x0400"), Err(QuantityError::LeadingZero));
+        assert_eq!(q("0x00"), Err(QuantityError::LeadingZero));
+        assert_eq!(q("0x+1"), Err(QuantityError::InvalidDigit));
+        assert_eq!(q("0x1g"), Err(QuantityError::InvalidDigit));
+        assert_eq!(q("0x1 "), Err(QuantityError::InvalidDigit));
+        assert_eq!(
+            q(&format!("0x1{}", "0".repeat(64))),
+            Err(QuantityError::Overflow)
+        );
+    }
+
     #[test]
     fn from_str_errors() {
         assert_eq!(
@@ -327,4 +704,262 @@ mod tests {
             &IntErrorKind::NegOverflow,
         );
     }
+
+    #[test]
+    fn from_str_error_offsets() {
+        let err =
+            |src: &str, radix, prefix| from_str_radix::<I256>(src, radix, prefix).unwrap_err();
+        assert_eq!(err("", 10, None).offset(), 0);
+        assert_eq!(err("-", 10, None).offset(), 1);
+        assert_eq!(err("+0b1", 16, Some("0x")).offset(), 1);
+        assert_eq!(err("12a4", 10, None).offset(), 2);
+        assert_eq!(err("-0x1fg", 16, Some("0x")).offset(), 5);
+        assert_eq!(err("-0x1fg", 16, Some("0x")).radix(), 16);
+
+        let max = format!("{}", I256::MAX);
+        let overflow = err(&format!("{max}0"), 10, None);
+        assert_eq!(overflow.kind(), &IntErrorKind::PosOverflow);
+        assert_eq!(overflow.offset(), max.len());
+
+        let trailing = err(&format!("{max}{max}?"), 10, None);
+        assert_eq!(trailing.kind(), &IntErrorKind::PosOverflow);
+        assert_eq!(trailing.offset(), max.len());
+    }
+
+    #[test]
+    fn fmt_matches_naive() {
+        use alloc::{string::String, vec::Vec};
+
+        fn naive(mut x: U256, radix: u32) -> String {
+            let base = U256::from(radix);
+            let mut digits = Vec::new();
+            loop {
+                digits.push(char::from_digit((x % base).as_u32(), radix).unwrap());
+                x /= base;
+                if x == 0 {
+                    break digits.into_iter().rev().collect();
+                }
+            }
+        }
+
+        let ten19 = U256::new(TEN19 as _);
+        let mut values = alloc::vec![
+            U256::ZERO,
+            U256::ONE,
+            U256::new(u64::MAX as _),
+            U256::new(u64::MAX as u128 + 1),
+            ten19 - 1,
+            ten19,
+            ten19 * ten19,
+            ten19 * ten19 + 1,
+            ten19 * ten19 * ten19 - 1,
+            U256::from_words(1, 0),
+            U256::from_words(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+            U256::MAX,
+        ];
+        values.extend((0..78).map(|i| U256::new(10).pow(i)));
+
+        for x in values {
+            assert_eq!(format!("{x}"), naive(x, 10));
+            assert_eq!(format!("{x:b}"), naive(x, 2));
+            assert_eq!(format!("{x:o}"), naive(x, 8));
+            assert_eq!(format!("{x:x}"), naive(x, 16));
+            assert_eq!(format!("{x:X}"), naive(x, 16).to_uppercase());
+            for radix in 2..=36 {
+                assert_eq!(x.to_str_radix_buf(radix).as_str(), naive(x, radix));
+            }
+            assert_eq!(
+                str::from_utf8(&x.to_hex_array()).unwrap(),
+                format!("{x:#066x}"),
+            );
+            assert_eq!(
+                str::from_utf8(&x.to_dec_array_padded()).unwrap(),
+                format!("{x:078}"),
+            );
+            assert_eq!(x.to_dec_array().as_str(), naive(x, 10));
+        }
+        for radix in 2..=36 {
+            assert_eq!(
+                I256::MIN.to_str_radix_buf(radix).as_str(),
+                format!("-{}", naive(I256::MIN.unsigned_abs(), radix)),
+            );
+        }
+        assert_eq!(
+            format!("{}", I256::MIN),
+            format!("-{}", naive(I256::MIN.unsigned_abs(), 10)),
+        );
+    }
+
+    #[test]
+    fn from_str_chunks() {
+        let values = [
+            U256::ZERO,
+            U256::new(u64::MAX as _),
+            U256::new(u64::MAX as u128 + 1),
+            U256::from_words(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+            U256::MAX - 1,
+            U256::MAX,
+        ];
+        for x in values {
+            assert_eq!(from_str_radix::<U256>(&format!("{x}"), 10, None), Ok(x));
+            assert_eq!(from_str_radix::<U256>(&format!("{x:b}"), 2, None), Ok(x));
+            assert_eq!(from_str_radix::<U256>(&format!("{x:o}"), 8, None), Ok(x));
+            assert_eq!(from_str_radix::<U256>(&format!("{x:x}"), 16, None), Ok(x));
+        }
+        for x in [I256::MIN, I256::MIN + 1, I256::MINUS_ONE, I256::MAX] {
+            assert_eq!(from_str_radix::<I256>(&format!("{x}"), 10, None), Ok(x));
+        }
+        assert_eq!(
+            from_str_radix::<U256>(&format!("{:0>100}", U256::MAX), 10, None),
+            Ok(U256::MAX),
+        );
+
+        let err = |src: &str| from_str_radix::<I256>(src, 10, None).unwrap_err();
+        let min = format!("{}", I256::MIN);
+        let underflow = err(&format!("{}9", &min[..min.len() - 1]));
+        assert_eq!(underflow.kind(), &IntErrorKind::NegOverflow);
+        assert_eq!(underflow.offset(), min.len() - 1);
+
+        // Errors are reported at the first invalid digit or overflow, even
+        // when they appear in the same chunk.
+        let invalid = err(&format!("{}?{}", "1".repeat(20), "1".repeat(10)));
+        assert_eq!(invalid.kind(), &IntErrorKind::InvalidDigit);
+        assert_eq!(invalid.offset(), 20);
+        let max = format!("{}", I256::MAX);
+        let overflow = err(&format!("{}1?", max));
+        assert_eq!(overflow.kind(), &IntErrorKind::PosOverflow);
+        assert_eq!(overflow.offset(), max.len());
+    }
+
+    #[test]
+    fn checked_mul_add_limbs() {
+        let muls = [0, 1, 10, 10_u64.pow(19), u64::MAX];
+        let adds = [0, 1, 9, u64::MAX];
+        for x in [
+            U256::ZERO,
+            U256::ONE,
+            U256::MAX >> 64,
+            U256::MAX >> 63,
+            U256::MAX,
+        ] {
+            for (mul, add) in muls.into_iter().flat_map(|m| adds.map(|a| (m, a))) {
+                let expected = x.checked_mul(U256::from(mul));
+                assert_eq!(
+                    x.checked_mul_add(mul, add),
+                    expected.and_then(|y| y.checked_add(U256::from(add))),
+                );
+                assert_eq!(
+                    x.checked_mul_sub(mul, add),
+                    expected.and_then(|y| y.checked_sub(U256::from(add))),
+                );
+            }
+        }
+        for x in [
+            I256::MIN,
+            I256::MIN >> 63,
+            I256::MIN >> 64,
+            I256::MINUS_ONE,
+            I256::ZERO,
+            I256::ONE,
+            I256::MAX >> 64,
+            I256::MAX >> 63,
+            I256::MAX,
+        ] {
+            for (mul, add) in muls.into_iter().flat_map(|m| adds.map(|a| (m, a))) {
+                let expected = x.checked_mul(I256::from(mul));
+                assert_eq!(
+                    x.checked_mul_add(mul, add),
+                    expected.and_then(|y| y.checked_add(I256::from(add))),
+                );
+                assert_eq!(
+                    x.checked_mul_sub(mul, add),
+                    expected.and_then(|y| y.checked_sub(I256::from(add))),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn from_str_lenient() {
+        let ok = |src: &str| super::from_str_lenient::<I256>(src).unwrap();
+        assert_eq!(ok("0"), 0);
+        assert_eq!(ok("+42"), 42);
+        assert_eq!(ok("-42"), -42);
+        assert_eq!(ok("1_000_000"), 1_000_000);
+        assert_eq!(ok("1__0_"), 10);
+        assert_eq!(ok("0b1010"), 10);
+        assert_eq!(ok("0B_1010"), 10);
+        assert_eq!(ok("0o777"), 0o777);
+        assert_eq!(ok("0O17"), 15);
+        assert_eq!(ok("0xdead_BEEF"), 0xdeadbeef_i128);
+        assert_eq!(ok("-0XfF"), -255);
+        assert_eq!(ok("0x1e5"), 0x1e5);
+        assert_eq!(ok("2e6"), 2_000_000);
+        assert_eq!(ok("2E+6"), 2_000_000);
+        assert_eq!(ok("1.5e18"), 1_500_000_000_000_000_000_i128);
+        assert_eq!(ok("1_000_000e18"), I256::new(10).pow(24));
+        assert_eq!(ok("1.50e1"), 15);
+        assert_eq!(ok("-1.000e0"), -1);
+        assert_eq!(ok("0e1_000_000"), 0);
+        assert_eq!(ok("5000e-3"), 5);
+        assert_eq!(ok("1_000e-3"), 1);
+        assert_eq!(ok("150e-1"), 15);
+        assert_eq!(ok("1.00e-0"), 1);
+        assert_eq!(ok("-2_500.00e-2"), -25);
+        assert_eq!(ok("0e-5"), 0);
+        assert_eq!(ok("0.0e-99999999999"), 0);
+        assert_eq!(ok(&format!("{}", I256::MIN)), I256::MIN);
+        assert_eq!(
+            super::from_str_lenient::<U256>("1.15792089237316195423570985008687907853269984665640564039457584007913129639935e77"),
+            Ok(U256::MAX),
+        );
+
+        let err = |src: &str| {
+            let err = super::from_str_lenient::<I256>(src).unwrap_err();
+            (*err.kind(), err.offset())
+        };
+        assert_eq!(err(""), (IntErrorKind::Empty, 0));
+        assert_eq!(err("+"), (IntErrorKind::InvalidDigit, 1));
+        assert_eq!(err("_1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err(" 1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err("0x"), (IntErrorKind::InvalidDigit, 2));
+        assert_eq!(err("0x_"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("0b102"), (IntErrorKind::InvalidDigit, 4));
+        assert_eq!(err("1.5"), (IntErrorKind::InvalidDigit, 1));
+        assert_eq!(err("0x1.5e1"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1.5e0"), (IntErrorKind::InvalidDigit, 2));
+        assert_eq!(err("1.55e1"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1.e1"), (IntErrorKind::InvalidDigit, 2));
+        assert_eq!(err(".5e1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err("1e"), (IntErrorKind::InvalidDigit, 2));
+        assert_eq!(err("1e-1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err("5e-1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err("150e-2"), (IntErrorKind::InvalidDigit, 1));
+        assert_eq!(err("1.50e-1"), (IntErrorKind::InvalidDigit, 0));
+        assert_eq!(err("10.5e-1"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1e-"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1e--1"), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1e1 "), (IntErrorKind::InvalidDigit, 3));
+        assert_eq!(err("1e77"), (IntErrorKind::PosOverflow, 1));
+        assert_eq!(err("-1e77"), (IntErrorKind::NegOverflow, 2));
+        assert_eq!(err("1e99999999999"), (IntErrorKind::PosOverflow, 11));
+        assert_eq!(
+            super::from_str_lenient::<U256>("-1").unwrap_err().kind(),
+            &IntErrorKind::InvalidDigit,
+        );
+    }
+
+    #[test]
+    fn from_ascii_radix() {
+        assert_eq!(U256::from_ascii(b"1234"), Ok(U256::new(1234)));
+        assert_eq!(I256::from_ascii_radix(b"-ff", 16), Ok(I256::new(-255)));
+        assert_eq!(
+            U256::from_ascii(format!("{}", U256::MAX).as_bytes()),
+            Ok(U256::MAX),
+        );
+
+        let err = U256::from_ascii(b"12\xc3\x28").unwrap_err();
+        assert_eq!(err.kind(), &IntErrorKind::InvalidDigit);
+        assert_eq!(err.offset(), 2);
+    }
 }
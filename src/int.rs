@@ -8,11 +8,24 @@ mod iter;
 mod ops;
 
 pub use self::convert::AsI256;
-use crate::uint::U256;
+use crate::{
+    error::{Integer, ParseError, TryFromIntError},
+    uint::U256,
+    FormatBuffer,
+};
 use core::num::ParseIntError;
 
 /// A 256-bit signed integer type.
 #[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::Immutable,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout
+    )
+)]
 #[repr(transparent)]
 pub struct I256(pub [i128; 2]);
 
@@ -129,6 +142,26 @@ impl I256 {
     /// assert_eq!(I256::from_str_hex("-0xa"), Ok(I256::new(-10)));
     /// ```
     pub fn from_str_hex(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_hex_detailed(src).map_err(ParseIntError::from)
+    }
+
+    /// Converts a prefixed string slice in base 16 to an integer, returning a
+    /// detailed error on failure.
+    ///
+    /// This behaves exactly like [`I256::from_str_hex`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// let err = I256::from_str_hex_detailed("0x2g").unwrap_err();
+    /// assert_eq!(err.offset(), 3);
+    /// ```
+    pub fn from_str_hex_detailed(src: &str) -> Result<Self, ParseError> {
         crate::fmt::from_str_radix(src, 16, Some("0x"))
     }
 
@@ -153,9 +186,82 @@ impl I256 {
     /// assert_eq!(I256::from_str_prefixed("-0xa"), Ok(I256::new(-10)));
     /// ```
     pub fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_prefixed_detailed(src).map_err(ParseIntError::from)
+    }
+
+    /// Converts a prefixed string slice in a base determined by the prefix to
+    /// an integer, returning a detailed error on failure.
+    ///
+    /// This behaves exactly like [`I256::from_str_prefixed`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// let err = I256::from_str_prefixed_detailed("10_000").unwrap_err();
+    /// assert_eq!(err.offset(), 2);
+    /// ```
+    pub fn from_str_prefixed_detailed(src: &str) -> Result<Self, ParseError> {
         crate::fmt::from_str_prefixed(src)
     }
 
+    /// Converts a string slice to an integer, accepting the same extended
+    /// grammar that humans commonly use for writing large numbers.
+    ///
+    /// In addition to plain decimal numbers, this accepts:
+    ///
+    /// * a leading `+` sign or a `-` sign for negative numbers
+    /// * `0b`, `0o` and `0x` prefixes in either letter case, for binary,
+    ///   octal and hexadecimal numbers
+    /// * `_` digit separators after the first digit or the prefix
+    /// * decimal scientific notation, such as `1.5e18`, `2E6` or `5000e-3`, as
+    ///   long as the result is an integer
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// assert_eq!(I256::from_str_lenient("-1_000"), Ok(I256::new(-1000)));
+    /// assert_eq!(I256::from_str_lenient("-0o17"), Ok(I256::new(-15)));
+    /// assert_eq!(I256::from_str_lenient("-2e6"), Ok(I256::new(-2_000_000)));
+    /// assert_eq!(I256::from_str_lenient("-250e-1"), Ok(I256::new(-25)));
+    /// assert!(I256::from_str_lenient("2.25e1").is_err());
+    /// ```
+    pub fn from_str_lenient(src: &str) -> Result<Self, ParseError> {
+        crate::fmt::from_str_lenient(src)
+    }
+
+    /// Formats the integer in the specified radix into a stack-allocated
+    /// buffer, without requiring an allocator.
+    ///
+    /// Negative numbers are formatted with a leading `-` sign, digits above 9
+    /// are represented with lower-case letters, and no prefix is added.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// assert_eq!(I256::new(-255).to_str_radix_buf(16).as_str(), "-ff");
+    /// assert_eq!(I256::new(71).to_str_radix_buf(36).as_str(), "1z");
+    /// ```
+    pub fn to_str_radix_buf(self, radix: u32) -> FormatBuffer<257> {
+        FormatBuffer::radix(self.unsigned_abs(), self < 0, radix)
+    }
+
     /// Cast to a primitive `i8`.
     pub const fn as_i8(self) -> i8 {
         let (_, lo) = self.into_words();
@@ -244,6 +350,100 @@ impl I256 {
         let sign = self.signum128() as f64;
         self.unsigned_abs().as_f64() * sign
     }
+
+    /// Converts a value to an integer, returning a detailed error on failure.
+    ///
+    /// This behaves exactly like [`TryFrom::try_from`], except that the
+    /// returned [`TryFromIntError`] additionally indicates whether the value
+    /// was too large or too small.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::{I256, U256};
+    /// use core::num::IntErrorKind;
+    ///
+    /// assert_eq!(I256::try_from_detailed(42_i8), Ok(I256::new(42)));
+    /// let err = I256::try_from_detailed(U256::MAX).unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::PosOverflow);
+    /// ```
+    pub fn try_from_detailed<T>(value: T) -> Result<Self, TryFromIntError>
+    where
+        T: Integer,
+        Self: TryFrom<T>,
+    {
+        Self::try_from(value).map_err(|_| {
+            if value.is_negative() {
+                TryFromIntError::neg_overflow()
+            } else {
+                TryFromIntError::pos_overflow()
+            }
+        })
+    }
+
+    /// Converts the integer to another type, returning a detailed error on
+    /// failure.
+    ///
+    /// This behaves exactly like [`TryFrom::try_from`], except that the
+    /// returned [`TryFromIntError`] additionally indicates whether the value
+    /// was too large or too small.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// use core::num::IntErrorKind;
+    ///
+    /// assert_eq!(I256::new(42).try_into_detailed::<u8>(), Ok(42));
+    /// let err = I256::MAX.try_into_detailed::<u8>().unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::PosOverflow);
+    /// ```
+    pub fn try_into_detailed<T>(self) -> Result<T, TryFromIntError>
+    where
+        T: TryFrom<Self>,
+    {
+        T::try_from(self).map_err(|_| {
+            if self.is_negative() {
+                TryFromIntError::neg_overflow()
+            } else {
+                TryFromIntError::pos_overflow()
+            }
+        })
+    }
+
+    /// Checked `self * mul + add`, returning `None` if overflow occurred.
+    #[inline]
+    pub(crate) fn checked_mul_add_u64(self, mul: u64, add: u64) -> Option<Self> {
+        let (x, carry) = self.as_u256().widening_mul_add_u64(mul, add);
+        Self::checked_from_wide(self, mul, x, carry as i128)
+    }
+
+    /// Checked `self * mul - sub`, returning `None` if overflow occurred.
+    #[inline]
+    pub(crate) fn checked_mul_sub_u64(self, mul: u64, sub: u64) -> Option<Self> {
+        let (x, carry) = self.as_u256().widening_mul_add_u64(mul, 0);
+        let (x, borrow) = x.overflowing_sub(U256::new(sub as _));
+        Self::checked_from_wide(self, mul, x, carry as i128 - borrow as i128)
+    }
+
+    /// Converts the result of a widening multiplication of the two's
+    /// complement limbs of `self` by `mul` back to a signed integer, returning
+    /// `None` if it does not fit in 256 bits.
+    #[inline]
+    fn checked_from_wide(self, mul: u64, low: U256, high: i128) -> Option<Self> {
+        // Multiplying the unsigned representation of a negative value adds an
+        // extra `mul * 2^256` to the product, which needs to be removed from
+        // the high limb. The result fits if the high limb is just the sign
+        // extension of the low 256 bits.
+        let high = high - if self.is_negative() { mul as i128 } else { 0 };
+        let x = low.as_i256();
+        let sign = if x.is_negative() { -1 } else { 0 };
+        (high == sign).then_some(x)
+    }
 }
 
 #[cfg(test)]
@@ -255,4 +455,13 @@ mod tests {
     fn converts_to_f64() {
         assert_eq!((-I256::from_words(1, 0)).as_f64(), -(2.0f64.powi(128)))
     }
+
+    #[test]
+    fn try_from_checks_bounds() {
+        assert_eq!(i8::try_from(I256::new(-128)), Ok(-128));
+        assert!(i8::try_from(I256::new(-129)).is_err());
+        assert!(u8::try_from(I256::MINUS_ONE).is_err());
+        assert!(u64::try_from(I256::new(i128::MIN)).is_err());
+        assert_eq!(u128::try_from(I256::new(i128::MAX)), Ok(i128::MAX as _));
+    }
 }
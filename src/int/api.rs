@@ -1,11 +1,8 @@
 //! Module containing integer aritimetic methods closely following the Rust
 //! standard library API for `iN` types.
 
-use crate::{fmt, intrinsics, I256, U256};
-use core::{
-    mem::{self, MaybeUninit},
-    num::ParseIntError,
-};
+use crate::{error::ParseError, fmt, intrinsics, I256, U256};
+use core::{mem, num::ParseIntError};
 
 impl I256 {
     /// The smallest value that can be represented by this integer type,
@@ -73,9 +70,76 @@ impl I256 {
     /// assert_eq!(I256::from_str_radix("A", 16), Ok(I256::new(10)));
     /// ```
     pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+        fmt::from_str_radix(src, radix, None).map_err(ParseIntError::from)
+    }
+
+    /// Converts a string slice in a given base to an integer, returning a
+    /// detailed error on failure.
+    ///
+    /// This behaves exactly like [`I256::from_str_radix`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// let err = I256::from_str_radix_detailed("12z4", 10).unwrap_err();
+    /// assert_eq!(err.offset(), 2);
+    /// ```
+    pub fn from_str_radix_detailed(src: &str, radix: u32) -> Result<Self, ParseError> {
         fmt::from_str_radix(src, radix, None)
     }
 
+    /// Converts a byte slice in a given base to an integer.
+    ///
+    /// This behaves exactly like [`I256::from_str_radix_detailed`], but parses
+    /// directly from ASCII bytes without requiring the input to be valid UTF-8
+    /// first. Error offsets are in bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// assert_eq!(I256::from_ascii_radix(b"A", 16), Ok(I256::new(10)));
+    /// assert!(I256::from_ascii_radix(b"1\xff", 16).is_err());
+    /// ```
+    #[inline]
+    pub fn from_ascii_radix(src: &[u8], radix: u32) -> Result<Self, ParseError> {
+        fmt::from_ascii_radix(src, radix, None)
+    }
+
+    /// Converts a byte slice in base 10 to an integer.
+    ///
+    /// This behaves exactly like parsing a string slice with
+    /// [`str::parse`], but parses directly from ASCII bytes without requiring
+    /// the input to be valid UTF-8 first, and returns a detailed error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// assert_eq!(I256::from_ascii(b"+42"), Ok(I256::new(42)));
+    /// ```
+    #[inline]
+    pub fn from_ascii(src: &[u8]) -> Result<Self, ParseError> {
+        fmt::from_ascii_radix(src, 10, None)
+    }
+
     /// Returns the number of ones in the binary representation of `self`.
     ///
     /// # Examples
@@ -208,9 +272,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn rotate_left(self, n: u32) -> Self {
-        let mut r = MaybeUninit::uninit();
-        intrinsics::signed::irol3(&mut r, &self, n);
-        unsafe { r.assume_init() }
+        intrinsics::signed::irol(&self, n)
     }
 
     /// Shifts the bits to the right by a specified amount, `n`,
@@ -237,9 +299,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn rotate_right(self, n: u32) -> Self {
-        let mut r = MaybeUninit::uninit();
-        intrinsics::signed::iror3(&mut r, &self, n);
-        unsafe { r.assume_init() }
+        intrinsics::signed::iror(&self, n)
     }
 
     /// Reverses the byte order of the integer.
@@ -512,9 +572,7 @@ impl I256 {
         if rhs == 0 || (self == Self::MIN && rhs == -1) {
             None
         } else {
-            let mut result = MaybeUninit::uninit();
-            intrinsics::signed::idiv3(&mut result, &self, &rhs);
-            Some(unsafe { result.assume_init() })
+            Some(intrinsics::signed::idiv(&self, &rhs))
         }
     }
 
@@ -562,9 +620,7 @@ impl I256 {
         if rhs == 0 || (self == Self::MIN && rhs == -1) {
             None
         } else {
-            let mut result = MaybeUninit::uninit();
-            intrinsics::signed::irem3(&mut result, &self, &rhs);
-            Some(unsafe { result.assume_init() })
+            Some(intrinsics::signed::irem(&self, &rhs))
         }
     }
 
@@ -731,7 +787,6 @@ impl I256 {
     /// assert_eq!(I256::MAX.saturating_add(I256::new(100)), I256::MAX);
     /// assert_eq!(I256::MIN.saturating_add(I256::new(-1)), I256::MIN);
     /// ```
-
     #[must_use = "this returns the result of the operation, \
                   without modifying the original"]
     #[inline]
@@ -791,7 +846,6 @@ impl I256 {
     /// assert_eq!(I256::MIN.saturating_neg(), I256::MAX);
     /// assert_eq!(I256::MAX.saturating_neg(), I256::MIN + 1);
     /// ```
-
     #[inline]
     pub fn saturating_neg(self) -> Self {
         I256::ZERO.saturating_sub(self)
@@ -811,7 +865,6 @@ impl I256 {
     /// assert_eq!(I256::MIN.saturating_abs(), I256::MAX);
     /// assert_eq!((I256::MIN + 1).saturating_abs(), I256::MAX);
     /// ```
-
     #[inline]
     pub fn saturating_abs(self) -> Self {
         if self.is_negative() {
@@ -918,9 +971,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_add(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::iadd3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::iadd(&self, &rhs)
     }
 
     /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around
@@ -939,9 +990,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_sub(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::isub3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::isub(&self, &rhs)
     }
 
     /// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping
@@ -960,9 +1009,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_mul(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::imul3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::imul(&self, &rhs)
     }
 
     /// Wrapping (modular) division. Computes `self / rhs`, wrapping around at
@@ -1123,9 +1170,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_shl(self, rhs: u32) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::ishl3(&mut result, &self, rhs & 0xff);
-        unsafe { result.assume_init() }
+        intrinsics::signed::ishl(&self, rhs & 0xff)
     }
 
     /// Panic-free bitwise shift-right; yields `self >> mask(rhs)`, where `mask`
@@ -1152,9 +1197,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_shr(self, rhs: u32) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::isar3(&mut result, &self, rhs & 0xff);
-        unsafe { result.assume_init() }
+        intrinsics::signed::isar(&self, rhs & 0xff)
     }
 
     /// Wrapping (modular) absolute value. Computes `self.abs()`, wrapping
@@ -1274,9 +1317,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::iaddc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::iaddo(&self, &rhs)
     }
 
     /// Calculates `self` - `rhs`
@@ -1298,9 +1339,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::isubc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::isubo(&self, &rhs)
     }
 
     /// Calculates the multiplication of `self` and `rhs`.
@@ -1322,9 +1361,7 @@ impl I256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::imulc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::imulo(&self, &rhs)
     }
 
     /// Calculates the divisor when `self` is divided by `rhs`.
@@ -1909,9 +1946,38 @@ impl I256 {
     /// ```
     #[inline]
     pub const fn to_ne_bytes(self) -> [u8; mem::size_of::<Self>()] {
+        #[cfg(not(feature = "safe"))]
         // SAFETY: integers are plain old datatypes so we can always transmute them to
         // arrays of bytes
-        unsafe { mem::transmute(self) }
+        return unsafe { mem::transmute::<Self, [u8; 32]>(self) };
+        #[cfg(feature = "safe")]
+        return intrinsics::cast::words_to_ne_bytes(self.as_u256().0);
+    }
+
+    /// Return a reference to the memory representation of this integer as a
+    /// byte array in little endian byte order, without copying.
+    ///
+    /// This is only available on little endian targets, where the in-memory
+    /// representation of the integer is little endian, and when the `safe`
+    /// feature is disabled. Use [`to_le_bytes`] for a portable alternative.
+    ///
+    /// [`to_le_bytes`]: #method.to_le_bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ethnum::I256;
+    /// let value = I256::new(0x1234);
+    /// assert_eq!(value.as_le_bytes(), &value.to_le_bytes());
+    /// assert_eq!(value.as_le_bytes()[..2], [0x34, 0x12]);
+    /// ```
+    #[cfg(all(target_endian = "little", not(feature = "safe")))]
+    #[inline]
+    pub const fn as_le_bytes(&self) -> &[u8; mem::size_of::<Self>()] {
+        // SAFETY: integers are plain old datatypes without padding, and
+        // byte arrays have no alignment requirements, so we can always
+        // reinterpret a reference to an integer as a reference to its bytes.
+        unsafe { &*(self as *const Self).cast() }
     }
 
     /// Create an integer value from its representation as a byte array in
@@ -2033,7 +2099,10 @@ impl I256 {
     /// ```
     #[inline]
     pub const fn from_ne_bytes(bytes: [u8; mem::size_of::<Self>()]) -> Self {
+        #[cfg(not(feature = "safe"))]
         // SAFETY: integers are plain old datatypes so we can always transmute to them
-        unsafe { mem::transmute(bytes) }
+        return unsafe { mem::transmute::<[u8; 32], Self>(bytes) };
+        #[cfg(feature = "safe")]
+        return U256(intrinsics::cast::words_from_ne_bytes(bytes)).as_i256();
     }
 }
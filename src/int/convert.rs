@@ -167,7 +167,7 @@ macro_rules! impl_try_into {
 
             #[inline]
             fn try_from(x: I256) -> Result<Self, Self::Error> {
-                if x <= <$t>::MAX.as_i256() {
+                if x >= <$t>::MIN.as_i256() && x <= <$t>::MAX.as_i256() {
                     Ok(*x.low() as _)
                 } else {
                     Err(tfie())
@@ -16,15 +16,15 @@ use crate::intrinsics::signed::*;
 
 impl_ops! {
     for I256 | i128 {
-        add => iadd2, iadd3, iaddc;
-        mul => imul2, imul3, imulc;
-        sub => isub2, isub3, isubc;
+        add => iadd2, iadd, iaddo;
+        mul => imul2, imul, imulo;
+        sub => isub2, isub, isubo;
 
-        div => idiv2, idiv3;
-        rem => irem2, irem3;
+        div => idiv2, idiv;
+        rem => irem2, irem;
 
-        shl => ishl2, ishl3;
-        shr => isar2, isar3;
+        shl => ishl2, ishl;
+        shr => isar2, isar;
     }
 }
 
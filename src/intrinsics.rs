@@ -8,30 +8,30 @@
 
 #![allow(missing_docs)]
 
-#[macro_use]
-mod cast;
+#[cfg(feature = "safe")]
+pub(crate) mod cast;
 
-#[cfg(feature = "llvm-intrinsics")]
+// NOTE: The `safe` feature takes precedence over `llvm-intrinsics`, so that
+// enabling both in a dependency graph falls back to the native intrinsics
+// instead of failing to build.
+#[cfg(all(feature = "llvm-intrinsics", not(feature = "safe")))]
 mod llvm;
-#[cfg(not(feature = "llvm-intrinsics"))]
+#[cfg(any(not(feature = "llvm-intrinsics"), feature = "safe"))]
 mod native;
 pub mod signed;
 
-#[cfg(feature = "llvm-intrinsics")]
+#[cfg(all(feature = "llvm-intrinsics", not(feature = "safe")))]
 pub use self::llvm::*;
-#[cfg(not(feature = "llvm-intrinsics"))]
+#[cfg(any(not(feature = "llvm-intrinsics"), feature = "safe"))]
 pub use self::native::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::uint::U256;
-    use core::mem::MaybeUninit;
 
     #[test]
     fn unchecked_addition() {
-        let mut res = MaybeUninit::uninit();
-        add3(&mut res, &U256([1, 2]), &U256([3, 0]));
-        assert_eq!(unsafe { res.assume_init() }, U256([4, 2]));
+        assert_eq!(add(&U256([1, 2]), &U256([3, 0])), U256([4, 2]));
     }
 }
@@ -1,17 +1,45 @@
 //! Module with casting helpers.
+//!
+//! These are safe replacements for reinterpreting 256-bit integers as bytes,
+//! used when the `safe` feature is enabled.
 
-/// Cast references of `U256` to `I256` for intrinsic implementations.
-macro_rules! cast {
-    (mut: $x:expr) => {
-        unsafe { &mut *($x as *mut $crate::int::I256).cast::<$crate::uint::U256>() }
-    };
-    (ref: $x:expr) => {
-        unsafe { &*($x as *const $crate::int::I256).cast::<$crate::uint::U256>() }
-    };
-    (uninit: $x:expr) => {
-        unsafe { &mut *($x).as_mut_ptr().cast::<MaybeUninit<$crate::uint::U256>>() }
-    };
-    (optuninit: $x:expr) => {
-        unsafe { ::core::mem::transmute(::core::ptr::read(&$x as *const _)) }
-    };
+/// Returns the memory representation of a pair of 128-bit words as a byte
+/// array.
+pub(crate) const fn words_to_ne_bytes(words: [u128; 2]) -> [u8; 32] {
+    let (a, b) = (words[0].to_ne_bytes(), words[1].to_ne_bytes());
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = a[i];
+        bytes[i + 16] = b[i];
+        i += 1;
+    }
+    bytes
+}
+
+/// Creates a pair of 128-bit words from its memory representation as a byte
+/// array.
+pub(crate) const fn words_from_ne_bytes(bytes: [u8; 32]) -> [u128; 2] {
+    let (mut a, mut b) = ([0; 16], [0; 16]);
+    let mut i = 0;
+    while i < 16 {
+        a[i] = bytes[i];
+        b[i] = bytes[i + 16];
+        i += 1;
+    }
+    [u128::from_ne_bytes(a), u128::from_ne_bytes(b)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let words = [0x0123456789abcdef_fedcba9876543210, u128::MAX - 42];
+        let bytes = words_to_ne_bytes(words);
+        assert_eq!(bytes[..16], words[0].to_ne_bytes());
+        assert_eq!(bytes[16..], words[1].to_ne_bytes());
+        assert_eq!(words_from_ne_bytes(bytes), words);
+    }
 }
@@ -9,7 +9,10 @@ mod divmod;
 #[allow(dead_code)]
 mod mul;
 
-pub use self::{divmod::*, mul::imulc};
+pub use self::{
+    divmod::*,
+    mul::{imulc, imulo},
+};
 use crate::{int::I256, uint::U256};
 use core::mem::{self, MaybeUninit};
 
@@ -64,6 +67,54 @@ def! {
     pub fn cttz(a: &U256) -> u32;
 }
 
+/// Define value-returning intrinsics in terms of the generated intrinsics with
+/// output parameters.
+macro_rules! ret {
+    ($(
+        pub fn $name:ident($($p:ident : $t:ty),*) -> $ret:ty => $f:ident;
+    )*) => {$(
+        #[inline]
+        pub fn $name($($p: $t),*) -> $ret {
+            let mut r = MaybeUninit::uninit();
+            $f(&mut r, $($p),*);
+            // SAFETY: Generated intrinsics always initialize their output.
+            unsafe { r.assume_init() }
+        }
+    )*};
+    ($(
+        pub fn $name:ident($($p:ident : $t:ty),*) -> ($ret:ty, bool) => $f:ident?;
+    )*) => {$(
+        #[inline]
+        pub fn $name($($p: $t),*) -> ($ret, bool) {
+            let mut r = MaybeUninit::uninit();
+            let overflow = $f(&mut r, $($p),*);
+            // SAFETY: Generated intrinsics always initialize their output.
+            (unsafe { r.assume_init() }, overflow)
+        }
+    )*};
+}
+
+ret! {
+    pub fn add(a: &U256, b: &U256) -> U256 => add3;
+    pub fn sub(a: &U256, b: &U256) -> U256 => sub3;
+    pub fn mul(a: &U256, b: &U256) -> U256 => mul3;
+
+    pub fn shl(a: &U256, b: u32) -> U256 => shl3;
+    pub fn sar(a: &I256, b: u32) -> I256 => sar3;
+    pub fn shr(a: &U256, b: u32) -> U256 => shr3;
+
+    pub fn rol(a: &U256, b: u32) -> U256 => rol3;
+    pub fn ror(a: &U256, b: u32) -> U256 => ror3;
+}
+
+ret! {
+    pub fn uaddo(a: &U256, b: &U256) -> (U256, bool) => uaddc?;
+    pub fn iaddo(a: &I256, b: &I256) -> (I256, bool) => iaddc?;
+    pub fn usubo(a: &U256, b: &U256) -> (U256, bool) => usubc?;
+    pub fn isubo(a: &I256, b: &I256) -> (I256, bool) => isubc?;
+    pub fn umulo(a: &U256, b: &U256) -> (U256, bool) => umulc?;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -11,26 +11,43 @@ pub fn add2(r: &mut U256, a: &U256) {
 }
 
 #[inline]
-pub fn add3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+pub fn add(a: &U256, b: &U256) -> U256 {
     let (lo, carry) = a.low().overflowing_add(*b.low());
     let hi = a.high().wrapping_add(carry as _).wrapping_add(*b.high());
 
-    r.write(U256::from_words(hi, lo));
+    U256::from_words(hi, lo)
 }
 
 #[inline]
-pub fn uaddc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+pub fn add3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+    r.write(add(a, b));
+}
+
+#[inline]
+pub fn uaddo(a: &U256, b: &U256) -> (U256, bool) {
     let (lo, carry_lo) = a.low().overflowing_add(*b.low());
     let (hi, carry_c) = a.high().overflowing_add(carry_lo as _);
     let (hi, carry_hi) = hi.overflowing_add(*b.high());
 
-    r.write(U256::from_words(hi, lo));
-    carry_c || carry_hi
+    (U256::from_words(hi, lo), carry_c || carry_hi)
+}
+
+#[inline]
+pub fn uaddc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+    let (s, overflow) = uaddo(a, b);
+    r.write(s);
+    overflow
+}
+
+#[inline]
+pub fn iaddo(a: &I256, b: &I256) -> (I256, bool) {
+    let s = add(&a.as_u256(), &b.as_u256()).as_i256();
+    (s, (*b >= 0 && s < *a) || (*b < 0 && s >= *a))
 }
 
 #[inline]
 pub fn iaddc(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) -> bool {
-    add3(cast!(uninit: r), cast!(ref: a), cast!(ref: b));
-    let s = unsafe { r.assume_init_ref() };
-    (*b >= 0 && s < a) || (*b < 0 && s >= a)
+    let (s, overflow) = iaddo(a, b);
+    r.write(s);
+    overflow
 }
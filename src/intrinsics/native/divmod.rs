@@ -80,13 +80,10 @@ fn udiv256_by_128_to_128(u1: u128, u0: u128, mut v: u128, r: &mut u128) -> u128
     q1 * B + q0
 }
 
+/// Computes the quotient of `a / b`, additionally writing the remainder to
+/// `rem` when it is requested.
 #[allow(clippy::many_single_char_names)]
-pub fn udivmod4(
-    res: &mut MaybeUninit<U256>,
-    a: &U256,
-    b: &U256,
-    rem: Option<&mut MaybeUninit<U256>>,
-) {
+pub fn udivmod(a: &U256, b: &U256, rem: Option<&mut U256>) -> U256 {
     // In the LLVM version on the x86_64 platform, `udiv256_by_128_to_128` would
     // defer to `divq` instruction, which divides a 128-bit value by a 64-bit
     // one returning a 64-bit value, making it very performant when dividing
@@ -102,10 +99,9 @@ pub fn udivmod4(
     // shortcut if the high and low values of the operands are 0:
     if a.high() | b.high() == 0 {
         if let Some(rem) = rem {
-            rem.write(U256::from_words(0, a.low() % b.low()));
+            *rem = U256::from_words(0, a.low() % b.low());
         }
-        res.write(U256::from_words(0, a.low() / b.low()));
-        return;
+        return U256::from_words(0, a.low() / b.low());
     }
 
     let dividend = *a;
@@ -115,10 +111,18 @@ pub fn udivmod4(
 
     if divisor > dividend {
         if let Some(rem) = rem {
-            rem.write(dividend);
+            *rem = dividend;
         }
-        res.write(U256::ZERO);
-        return;
+        return U256::ZERO;
+    }
+    // Word-sized divisors can be handled with a chain of 128-by-64-bit
+    // divisions instead, one for each 64-bit limb of the dividend.
+    if *divisor.high() == 0 && *divisor.low() <= u64::MAX as u128 {
+        let (q, r) = dividend.div_rem_u64(*divisor.low() as u64);
+        if let Some(rem) = rem {
+            *rem = U256::new(r as _);
+        }
+        return q;
     }
     // When the divisor fits in 128 bits, we can use an optimized path.
     if *divisor.high() == 0 {
@@ -148,18 +152,36 @@ pub fn udivmod4(
             );
         }
         if let Some(rem) = rem {
-            rem.write(remainder);
+            *rem = remainder;
         }
-        res.write(quotient);
-        return;
+        return quotient;
     }
 
     (quotient, remainder) = div_mod_knuth(&dividend, &divisor);
 
     if let Some(rem) = rem {
-        rem.write(remainder);
+        *rem = remainder;
+    }
+    quotient
+}
+
+#[allow(clippy::many_single_char_names)]
+pub fn udivmod4(
+    res: &mut MaybeUninit<U256>,
+    a: &U256,
+    b: &U256,
+    rem: Option<&mut MaybeUninit<U256>>,
+) {
+    match rem {
+        Some(rem) => {
+            let mut r = U256::ZERO;
+            res.write(udivmod(a, b, Some(&mut r)));
+            rem.write(r);
+        }
+        None => {
+            res.write(udivmod(a, b, None));
+        }
     }
-    res.write(quotient);
 }
 
 // See Knuth, TAOCP, Volume 2, section 4.3.1, Algorithm D.
@@ -346,92 +368,110 @@ pub fn div_mod_knuth(u: &U256, v: &U256) -> (U256, U256) {
     (q, remainder)
 }
 
+#[inline]
+pub fn udiv(a: &U256, b: &U256) -> U256 {
+    udivmod(a, b, None)
+}
+
 #[inline]
 pub fn udiv2(r: &mut U256, a: &U256) {
-    let (a, b) = (*r, a);
-    // SAFETY: `udivmod4` does not write `MaybeUninit::uninit()` to `res` and
-    // `U256` does not implement `Drop`.
-    let res = unsafe { &mut *(r as *mut U256).cast() };
-    udivmod4(res, &a, b, None);
+    *r = udiv(r, a);
 }
 
 #[inline]
 pub fn udiv3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
-    udivmod4(r, a, b, None);
+    r.write(udiv(a, b));
+}
+
+#[inline]
+pub fn urem(a: &U256, b: &U256) -> U256 {
+    let mut rem = U256::ZERO;
+    udivmod(a, b, Some(&mut rem));
+    rem
 }
 
 #[inline]
 pub fn urem2(r: &mut U256, a: &U256) {
-    let mut res = MaybeUninit::uninit();
-    let (a, b) = (*r, a);
-    // SAFETY: `udivmod4` does not write `MaybeUninit::uninit()` to `rem` and
-    // `U256` does not implement `Drop`.
-    let r = unsafe { &mut *(r as *mut U256).cast() };
-    udivmod4(&mut res, &a, b, Some(r));
+    *r = urem(r, a);
 }
 
 #[inline]
 pub fn urem3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
-    let mut res = MaybeUninit::uninit();
-    udivmod4(&mut res, a, b, Some(r));
+    r.write(urem(a, b));
 }
 
-pub fn idivmod4(
-    res: &mut MaybeUninit<I256>,
-    a: &I256,
-    b: &I256,
-    rem: Option<&mut MaybeUninit<I256>>,
-) {
+/// Computes the quotient of `a / b`, additionally writing the remainder to
+/// `rem` when it is requested.
+pub fn idivmod(a: &I256, b: &I256, rem: Option<&mut I256>) -> I256 {
     const BITS_IN_TWORD_M1: u32 = 255;
     let s_a = a >> BITS_IN_TWORD_M1; // s_a = a < 0 ? -1 : 0
     let mut s_b = b >> BITS_IN_TWORD_M1; // s_b = b < 0 ? -1 : 0
     let a = (a ^ s_a).wrapping_sub(s_a); // negate if s_a == -1
     let b = (b ^ s_b).wrapping_sub(s_b); // negate if s_b == -1
     s_b ^= s_a; // sign of quotient
-    udivmod4(
-        cast!(uninit: res),
-        cast!(ref: &a),
-        cast!(ref: &b),
-        cast!(optuninit: rem),
-    );
-    let q = unsafe { res.assume_init_ref() };
-    let q = (q ^ s_b).wrapping_sub(s_b); // negate if s_b == -1
-    res.write(q);
-    if let Some(rem) = rem {
-        let r = unsafe { rem.assume_init_ref() };
-        let r = (r ^ s_a).wrapping_sub(s_a);
-        rem.write(r);
+    let (a, b) = (a.as_u256(), b.as_u256());
+    let q = match rem {
+        Some(rem) => {
+            let mut r = U256::ZERO;
+            let q = udivmod(&a, &b, Some(&mut r));
+            let r = r.as_i256();
+            *rem = (r ^ s_a).wrapping_sub(s_a); // negate if s_a == -1
+            q
+        }
+        None => udivmod(&a, &b, None),
+    };
+    let q = q.as_i256();
+    (q ^ s_b).wrapping_sub(s_b) // negate if s_b == -1
+}
+
+pub fn idivmod4(
+    res: &mut MaybeUninit<I256>,
+    a: &I256,
+    b: &I256,
+    rem: Option<&mut MaybeUninit<I256>>,
+) {
+    match rem {
+        Some(rem) => {
+            let mut r = I256::ZERO;
+            res.write(idivmod(a, b, Some(&mut r)));
+            rem.write(r);
+        }
+        None => {
+            res.write(idivmod(a, b, None));
+        }
     }
 }
 
+#[inline]
+pub fn idiv(a: &I256, b: &I256) -> I256 {
+    idivmod(a, b, None)
+}
+
 #[inline]
 pub fn idiv2(r: &mut I256, a: &I256) {
-    let (a, b) = (*r, a);
-    // SAFETY: `udivmod4` does not write `MaybeUninit::uninit()` to `res` and
-    // `U256` does not implement `Drop`.
-    let res = unsafe { &mut *(r as *mut I256).cast() };
-    idivmod4(res, &a, b, None);
+    *r = idiv(r, a);
 }
 
 #[inline]
 pub fn idiv3(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) {
-    idivmod4(r, a, b, None);
+    r.write(idiv(a, b));
+}
+
+#[inline]
+pub fn irem(a: &I256, b: &I256) -> I256 {
+    let mut rem = I256::ZERO;
+    idivmod(a, b, Some(&mut rem));
+    rem
 }
 
 #[inline]
 pub fn irem2(r: &mut I256, a: &I256) {
-    let mut res = MaybeUninit::uninit();
-    let (a, b) = (*r, a);
-    // SAFETY: `udivmod4` does not write `MaybeUninit::uninit()` to `rem` and
-    // `U256` does not implement `Drop`.
-    let r = unsafe { &mut *(r as *mut I256).cast() };
-    idivmod4(&mut res, &a, b, Some(r));
+    *r = irem(r, a);
 }
 
 #[inline]
 pub fn irem3(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) {
-    let mut res = MaybeUninit::uninit();
-    idivmod4(&mut res, a, b, Some(r));
+    r.write(irem(a, b));
 }
 
 #[cfg(test)]
@@ -440,15 +480,11 @@ mod tests {
     use crate::AsU256;
 
     fn udiv(a: impl AsU256, b: impl AsU256) -> U256 {
-        let mut r = MaybeUninit::uninit();
-        udiv3(&mut r, &a.as_u256(), &b.as_u256());
-        unsafe { r.assume_init() }
+        super::udiv(&a.as_u256(), &b.as_u256())
     }
 
     fn urem(a: impl AsU256, b: impl AsU256) -> U256 {
-        let mut r = MaybeUninit::uninit();
-        urem3(&mut r, &a.as_u256(), &b.as_u256());
-        unsafe { r.assume_init() }
+        super::urem(&a.as_u256(), &b.as_u256())
     }
 
     #[test]
@@ -33,26 +33,27 @@ pub fn umulddi3(a: &u128, b: &u128) -> U256 {
 
 #[inline]
 pub fn mul2(r: &mut U256, a: &U256) {
-    let (a, b) = (*r, a);
-    // SAFETY: `multi3` does not write `MaybeUninit::uninit()` to `res` and
-    // `U256` does not implement `Drop`.
-    let res = unsafe { &mut *(r as *mut U256).cast() };
-    mul3(res, &a, b);
+    *r = mul(r, a);
 }
 
 #[inline]
-pub fn mul3(res: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+pub fn mul(a: &U256, b: &U256) -> U256 {
     let mut r = umulddi3(a.low(), b.low());
 
     let hi_lo = a.high().wrapping_mul(*b.low());
     let lo_hi = a.low().wrapping_mul(*b.high());
     *r.high_mut() = r.high().wrapping_add(hi_lo.wrapping_add(lo_hi));
 
-    res.write(r);
+    r
 }
 
 #[inline]
-pub fn umulc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+pub fn mul3(res: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+    res.write(mul(a, b));
+}
+
+#[inline]
+pub fn umulo(a: &U256, b: &U256) -> (U256, bool) {
     let mut res = umulddi3(a.low(), b.low());
 
     let (hi_lo, overflow_hi_lo) = a.high().overflowing_mul(*b.low());
@@ -63,13 +64,34 @@ pub fn umulc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
 
     let overflow_hi_hi = (*a.high() != 0) & (*b.high() != 0);
 
+    (
+        res,
+        overflow_hi_lo | overflow_lo_hi | overflow_hi | overflow_high | overflow_hi_hi,
+    )
+}
+
+#[inline]
+pub fn umulc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+    let (res, overflow) = umulo(a, b);
     r.write(res);
-    overflow_hi_lo | overflow_lo_hi | overflow_hi | overflow_high | overflow_hi_hi
+    overflow
+}
+
+#[inline]
+pub fn imulo(a: &I256, b: &I256) -> (I256, bool) {
+    let res = mul(&a.as_u256(), &b.as_u256()).as_i256();
+    (res, imul_overflows(a, b))
 }
 
 #[inline]
 pub fn imulc(res: &mut MaybeUninit<I256>, a: &I256, b: &I256) -> bool {
-    mul3(cast!(uninit: res), cast!(ref: a), cast!(ref: b));
+    let (r, overflow) = imulo(a, b);
+    res.write(r);
+    overflow
+}
+
+#[inline]
+fn imul_overflows(a: &I256, b: &I256) -> bool {
     if *a == I256::MIN {
         return *b != 0 && *b != 1;
     }
@@ -96,9 +118,7 @@ mod tests {
     use crate::AsU256;
 
     fn umul(a: impl AsU256, b: impl AsU256) -> (U256, bool) {
-        let mut r = MaybeUninit::uninit();
-        let overflow = umulc(&mut r, &a.as_u256(), &b.as_u256());
-        (unsafe { r.assume_init() }, overflow)
+        umulo(&a.as_u256(), &b.as_u256())
     }
 
     #[test]
@@ -4,12 +4,22 @@
 use crate::uint::U256;
 use core::mem::MaybeUninit;
 
+#[inline]
+pub fn rol(a: &U256, b: u32) -> U256 {
+    (a << (b & 0xff)) | (a >> ((256 - b) & 0xff))
+}
+
 #[inline]
 pub fn rol3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
-    r.write((a << (b & 0xff)) | (a >> ((256 - b) & 0xff)));
+    r.write(rol(a, b));
+}
+
+#[inline]
+pub fn ror(a: &U256, b: u32) -> U256 {
+    (a >> (b & 0xff)) | (a << ((256 - b) & 0xff))
 }
 
 #[inline]
 pub fn ror3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
-    r.write((a >> (b & 0xff)) | (a << ((256 - b) & 0xff)));
+    r.write(ror(a, b));
 }
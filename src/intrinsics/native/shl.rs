@@ -19,7 +19,7 @@ pub fn shl2(r: &mut U256, a: u32) {
 }
 
 #[inline]
-pub fn shl3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
+pub fn shl(a: &U256, b: u32) -> U256 {
     debug_assert!(b < 256, "shl intrinsic called with overflowing shift");
 
     let (hi, lo) = if b == 0 {
@@ -30,5 +30,10 @@ pub fn shl3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
         (a.low() << (b & 0x7f), 0)
     };
 
-    r.write(U256::from_words(hi, lo));
+    U256::from_words(hi, lo)
+}
+
+#[inline]
+pub fn shl3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
+    r.write(shl(a, b));
 }
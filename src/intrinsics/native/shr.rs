@@ -22,7 +22,7 @@ pub fn sar2(r: &mut I256, a: u32) {
 }
 
 #[inline]
-pub fn sar3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
+pub fn sar(a: &I256, b: u32) -> I256 {
     debug_assert!(b < 256, "shr intrinsic called with overflowing shift");
 
     let (hi, lo) = if b == 0 {
@@ -36,7 +36,12 @@ pub fn sar3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
         (a.high() >> 127, a.high() >> (b & 0x7f))
     };
 
-    r.write(I256::from_words(hi, lo));
+    I256::from_words(hi, lo)
+}
+
+#[inline]
+pub fn sar3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
+    r.write(sar(a, b));
 }
 
 #[inline]
@@ -55,7 +60,7 @@ pub fn shr2(r: &mut U256, a: u32) {
 }
 
 #[inline]
-pub fn shr3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
+pub fn shr(a: &U256, b: u32) -> U256 {
     debug_assert!(b < 256, "shr intrinsic called with overflowing shift");
 
     let (hi, lo) = if b == 0 {
@@ -66,5 +71,10 @@ pub fn shr3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
         (0, a.high() >> (b & 0x7f))
     };
 
-    r.write(U256::from_words(hi, lo));
+    U256::from_words(hi, lo)
+}
+
+#[inline]
+pub fn shr3(r: &mut MaybeUninit<U256>, a: &U256, b: u32) {
+    r.write(shr(a, b));
 }
@@ -11,26 +11,43 @@ pub fn sub2(r: &mut U256, a: &U256) {
 }
 
 #[inline]
-pub fn sub3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+pub fn sub(a: &U256, b: &U256) -> U256 {
     let (lo, carry) = a.low().overflowing_sub(*b.low());
     let hi = a.high().wrapping_sub(carry as _).wrapping_sub(*b.high());
 
-    r.write(U256::from_words(hi, lo));
+    U256::from_words(hi, lo)
 }
 
 #[inline]
-pub fn usubc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+pub fn sub3(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) {
+    r.write(sub(a, b));
+}
+
+#[inline]
+pub fn usubo(a: &U256, b: &U256) -> (U256, bool) {
     let (lo, carry_lo) = a.low().overflowing_sub(*b.low());
     let (hi, carry_c) = a.high().overflowing_sub(carry_lo as _);
     let (hi, carry_hi) = hi.overflowing_sub(*b.high());
 
-    r.write(U256::from_words(hi, lo));
-    carry_c || carry_hi
+    (U256::from_words(hi, lo), carry_c || carry_hi)
+}
+
+#[inline]
+pub fn usubc(r: &mut MaybeUninit<U256>, a: &U256, b: &U256) -> bool {
+    let (s, overflow) = usubo(a, b);
+    r.write(s);
+    overflow
+}
+
+#[inline]
+pub fn isubo(a: &I256, b: &I256) -> (I256, bool) {
+    let s = sub(&a.as_u256(), &b.as_u256()).as_i256();
+    (s, (*b >= 0 && s > *a) || (*b < 0 && s <= *a))
 }
 
 #[inline]
 pub fn isubc(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) -> bool {
-    sub3(cast!(uninit: r), cast!(ref: a), cast!(ref: b));
-    let s = unsafe { r.assume_init_ref() };
-    (*b >= 0 && s > a) || (*b < 0 && s <= a)
+    let (s, overflow) = isubo(a, b);
+    r.write(s);
+    overflow
 }
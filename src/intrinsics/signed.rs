@@ -4,72 +4,104 @@
 //! This module can be helpful when using intrinsics directly.
 
 pub use super::{
-    add2 as uadd2, add3 as uadd3, ctlz as uctlz, cttz as ucttz, iaddc, idiv2, idiv3, imulc, irem2,
-    irem3, isubc, mul2 as umul2, mul3 as umul3, rol3 as urol3, ror3 as uror3, sar2 as isar2,
-    sar3 as isar3, shl2 as ushl2, shl3 as ushl3, shr2 as ushr2, shr3 as ushr3, sub2 as usub2,
-    sub3 as usub3, uaddc, udiv2, udiv3, umulc, urem2, urem3, usubc,
+    add as uadd, add2 as uadd2, add3 as uadd3, ctlz as uctlz, cttz as ucttz, iaddc, iaddo, idiv,
+    idiv2, idiv3, imulc, imulo, irem, irem2, irem3, isubc, isubo, mul as umul, mul2 as umul2,
+    mul3 as umul3, rol as urol, rol3 as urol3, ror as uror, ror3 as uror3, sar as isar,
+    sar2 as isar2, sar3 as isar3, shl as ushl, shl2 as ushl2, shl3 as ushl3, shr as ushr,
+    shr2 as ushr2, shr3 as ushr3, sub as usub, sub2 as usub2, sub3 as usub3, uaddc, uaddo, udiv,
+    udiv2, udiv3, umulc, umulo, urem, urem2, urem3, usubc, usubo,
 };
 use crate::int::I256;
 use core::mem::MaybeUninit;
 
+#[inline]
+pub fn iadd(a: &I256, b: &I256) -> I256 {
+    super::add(&a.as_u256(), &b.as_u256()).as_i256()
+}
+
 #[inline]
 pub fn iadd2(r: &mut I256, a: &I256) {
-    super::add2(cast!(mut: r), cast!(ref: a));
+    *r = iadd(r, a);
 }
 
 #[inline]
 pub fn iadd3(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) {
-    super::add3(cast!(uninit: r), cast!(ref: a), cast!(ref: b));
+    r.write(iadd(a, b));
+}
+
+#[inline]
+pub fn isub(a: &I256, b: &I256) -> I256 {
+    super::sub(&a.as_u256(), &b.as_u256()).as_i256()
 }
 
 #[inline]
 pub fn isub2(r: &mut I256, a: &I256) {
-    super::sub2(cast!(mut: r), cast!(ref: a));
+    *r = isub(r, a);
 }
 
 #[inline]
 pub fn isub3(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) {
-    super::sub3(cast!(uninit: r), cast!(ref: a), cast!(ref: b));
+    r.write(isub(a, b));
+}
+
+#[inline]
+pub fn imul(a: &I256, b: &I256) -> I256 {
+    super::mul(&a.as_u256(), &b.as_u256()).as_i256()
 }
 
 #[inline]
 pub fn imul2(r: &mut I256, a: &I256) {
-    super::mul2(cast!(mut: r), cast!(ref: a));
+    *r = imul(r, a);
 }
 
 #[inline]
 pub fn imul3(r: &mut MaybeUninit<I256>, a: &I256, b: &I256) {
-    super::mul3(cast!(uninit: r), cast!(ref: a), cast!(ref: b));
+    r.write(imul(a, b));
+}
+
+#[inline]
+pub fn ishl(a: &I256, b: u32) -> I256 {
+    super::shl(&a.as_u256(), b).as_i256()
 }
 
 #[inline]
 pub fn ishl2(r: &mut I256, a: u32) {
-    super::shl2(cast!(mut: r), a);
+    *r = ishl(r, a);
 }
 
 #[inline]
 pub fn ishl3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
-    super::shl3(cast!(uninit: r), cast!(ref: a), b);
+    r.write(ishl(a, b));
+}
+
+#[inline]
+pub fn irol(a: &I256, b: u32) -> I256 {
+    super::rol(&a.as_u256(), b).as_i256()
 }
 
 #[inline]
 pub fn irol3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
-    super::rol3(cast!(uninit: r), cast!(ref: a), b);
+    r.write(irol(a, b));
+}
+
+#[inline]
+pub fn iror(a: &I256, b: u32) -> I256 {
+    super::ror(&a.as_u256(), b).as_i256()
 }
 
 #[inline]
 pub fn iror3(r: &mut MaybeUninit<I256>, a: &I256, b: u32) {
-    super::ror3(cast!(uninit: r), cast!(ref: a), b);
+    r.write(iror(a, b));
 }
 
 #[inline]
 pub fn ictlz(a: &I256) -> u32 {
-    super::ctlz(cast!(ref: a))
+    super::ctlz(&a.as_u256())
 }
 
 #[inline]
 pub fn icttz(a: &I256) -> u32 {
-    super::cttz(cast!(ref: a))
+    super::cttz(&a.as_u256())
 }
 
 #[cfg(test)]
@@ -3,11 +3,29 @@
 //! The implementation tries to follow as closely as possible to primitive
 //! integer types, and should implement all the common methods and traits as the
 //! primitive integer types.
+//!
+//! # Features
+//!
+//! The `safe` feature builds the crate without any `unsafe` code at all, at a
+//! small cost in formatting and byte conversion performance. This is enforced
+//! with `#![forbid(unsafe_code)]`, so it can not be overridden. As a result:
+//!
+//! - `as_le_bytes`, which reinterprets a reference to an integer as a
+//!   reference to its bytes, is not available; use `to_le_bytes` instead.
+//! - It can not be combined with the `bytemuck` feature, as implementing its
+//!   traits inherently requires `unsafe` code.
+//! - When combined with `llvm-intrinsics`, the `safe` feature takes precedence
+//!   and the native Rust intrinsics are used instead, so that enabling both
+//!   features somewhere in a dependency graph does not fail to build.
 
 #![deny(missing_docs)]
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
 #![no_std]
 
-#[cfg(test)]
+#[cfg(all(feature = "safe", feature = "bytemuck"))]
+compile_error!("the `safe` feature is incompatible with `bytemuck`");
+
+#[cfg(any(test, feature = "ssz"))]
 extern crate alloc;
 
 #[macro_use]
@@ -22,13 +40,42 @@ mod macros {
     pub mod iter;
 }
 
+pub mod abi;
+#[cfg(feature = "alloy-primitives")]
+mod alloy_primitives;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod buffer;
+#[cfg(all(feature = "bytemuck", not(feature = "safe")))]
+mod bytemuck;
+mod divisor;
 mod error;
 mod fmt;
 mod int;
 pub mod intrinsics;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+#[cfg(feature = "num-traits")]
+mod num_traits;
+#[cfg(feature = "primitive-types")]
+mod primitive_types;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rand")]
+pub mod rand;
+#[cfg(feature = "ruint")]
+mod ruint;
+#[cfg(feature = "scale")]
+pub mod scale;
 #[cfg(feature = "serde")]
 pub mod serde;
+#[cfg(feature = "ssz")]
+pub mod ssz;
+#[cfg(feature = "subtle")]
+pub mod subtle;
 mod uint;
+#[cfg(feature = "zeroize")]
+mod zeroize;
 
 /// Convenience re-export of 256-integer types and as- conversion traits.
 pub mod prelude {
@@ -36,6 +83,9 @@ pub mod prelude {
 }
 
 pub use crate::{
+    buffer::FormatBuffer,
+    divisor::Divisor256,
+    error::{ParseError, QuantityError, TryFromIntError},
     int::{AsI256, I256},
     uint::{AsU256, U256},
 };
@@ -9,6 +9,14 @@ macro_rules! impl_fmt {
                 Self::from(u)
             }
             #[inline]
+            fn from_u64(u: u64) -> Self {
+                Self::from(u)
+            }
+            #[inline]
+            fn checked_neg(&self) -> Option<Self> {
+                Self::checked_neg(*self)
+            }
+            #[inline]
             fn checked_mul(&self, other: u32) -> Option<Self> {
                 Self::checked_mul(*self, Self::from(other))
             }
@@ -20,6 +28,14 @@ macro_rules! impl_fmt {
             fn checked_add(&self, other: u32) -> Option<Self> {
                 Self::checked_add(*self, Self::from(other))
             }
+            #[inline]
+            fn checked_mul_add(&self, mul: u64, add: u64) -> Option<Self> {
+                Self::checked_mul_add_u64(*self, mul, add)
+            }
+            #[inline]
+            fn checked_mul_sub(&self, mul: u64, sub: u64) -> Option<Self> {
+                Self::checked_mul_sub_u64(*self, mul, sub)
+            }
         }
 
         impl ::core::str::FromStr for $int {
@@ -27,7 +43,7 @@ macro_rules! impl_fmt {
 
             #[inline]
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                $crate::fmt::from_str_radix(s, 10, None)
+                $crate::fmt::from_str_radix(s, 10, None).map_err(Self::Err::from)
             }
         }
 
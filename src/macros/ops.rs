@@ -3,26 +3,26 @@
 macro_rules! impl_ops {
     (
         for $int:ident | $prim:ident {
-            add => $add2:ident, $add3:ident, $addc:ident;
-            mul => $mul2:ident, $mul3:ident, $mulc:ident;
-            sub => $sub2:ident, $sub3:ident, $subc:ident;
-            div => $div2:ident, $div3:ident;
-            rem => $rem2:ident, $rem3:ident;
-            shl => $shl2:ident, $shl3:ident;
-            shr => $shr2:ident, $shr3:ident;
+            add => $add2:ident, $add:ident, $addo:ident;
+            mul => $mul2:ident, $mul:ident, $mulo:ident;
+            sub => $sub2:ident, $sub:ident, $subo:ident;
+            div => $div2:ident, $div:ident;
+            rem => $rem2:ident, $rem:ident;
+            shl => $shl2:ident, $shl:ident;
+            shr => $shr2:ident, $shr:ident;
         }
     ) => {
         __impl_ops_binop! {
             for $int | $prim
 
             impl Add {
-                + add => $add3, $addc; "add with overflow"
+                + add => $add, $addo; "add with overflow"
             }
             impl Mul {
-                * mul => $mul3, $mulc; "multiply with overflow"
+                * mul => $mul, $mulo; "multiply with overflow"
             }
             impl Sub {
-                - sub => $sub3, $subc; "subtract with overflow"
+                - sub => $sub, $subo; "subtract with overflow"
             }
         }
 
@@ -30,10 +30,10 @@ macro_rules! impl_ops {
             for $int | $prim
 
             impl Div {
-                / div => $div3; "divide by zero"
+                / div => $div; "divide by zero"
             }
             impl Rem {
-                % rem => $rem3; "calculate the remainder with a divisor of zero"
+                % rem => $rem; "calculate the remainder with a divisor of zero"
             }
         }
 
@@ -41,10 +41,10 @@ macro_rules! impl_ops {
             for $int
 
             impl Shl {
-                << shl => $shl3; "shift left with overflow"
+                << shl => $shl; "shift left with overflow"
             }
             impl Shr {
-                >> shr => $shr3; "shift right with overflow"
+                >> shr => $shr; "shift right with overflow"
             }
         }
 
@@ -147,7 +147,7 @@ macro_rules! __impl_ops_binop {
         for $int:ident | $prim:ident
         $(
             impl $op:ident {
-                $x:tt $method:ident => $op3:path, $opc:path; $msg:expr
+                $x:tt $method:ident => $f:path, $fo:path; $msg:expr
             }
         )*
     ) => {$(
@@ -156,18 +156,17 @@ macro_rules! __impl_ops_binop {
 
             #[inline]
             fn $method(self, rhs: Self) -> Self::Output {
-                let mut result = ::core::mem::MaybeUninit::uninit();
                 #[cfg(not(debug_assertions))]
-                {
-                    $op3(&mut result, self, rhs);
-                }
+                let result = $f(self, rhs);
                 #[cfg(debug_assertions)]
-                {
-                    if $opc(&mut result, self, rhs) {
+                let result = {
+                    let (result, overflow) = $fo(self, rhs);
+                    if overflow {
                         panic!(concat!("attempt to ", $msg));
                     }
-                }
-                unsafe { result.assume_init() }
+                    result
+                };
+                result
             }
         }
 
@@ -224,7 +223,7 @@ macro_rules! __impl_ops_divmod {
         for $int:ident | $prim:ident
         $(
             impl $op:ident {
-                $x:tt $method:ident => $op3:path; $msg:expr
+                $x:tt $method:ident => $f:path; $msg:expr
             }
         )*
     ) => {$(
@@ -237,9 +236,7 @@ macro_rules! __impl_ops_divmod {
                     panic!(concat!("attempt to ", $msg));
                 }
 
-                let mut result = ::core::mem::MaybeUninit::uninit();
-                $op3(&mut result, self, rhs);
-                unsafe { result.assume_init() }
+                $f(self, rhs)
             }
         }
 
@@ -254,7 +251,7 @@ macro_rules! __impl_ops_shift {
         for $int:ident
         $(
             impl $op:ident {
-                $x:tt $method:ident => $op3:path; $msg:expr
+                $x:tt $method:ident => $f:path; $msg:expr
             }
         )*
     ) => {$(
@@ -268,10 +265,7 @@ macro_rules! __impl_ops_shift {
                     panic!(concat!("attempt to ", $msg));
                 }
 
-                let mut result = ::core::mem::MaybeUninit::uninit();
-                $op3(&mut result, self, rhs);
-
-                unsafe { result.assume_init() }
+                $f(self, rhs)
             }
         }
 
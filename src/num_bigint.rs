@@ -0,0 +1,161 @@
+//! Conversions between 256-bit integer types and `num-bigint` big integers.
+//!
+//! Conversions into big integers are lossless. Conversions back are fallible,
+//! and fail if the big integer does not fit in the 256-bit integer type:
+//!
+//! ```
+//! # use ethnum::{I256, U256};
+//! # use num_bigint::{BigInt, BigUint};
+//! let n = BigUint::from(U256::MAX) + 1_u32;
+//! assert!(U256::try_from(&n).is_err());
+//! assert_eq!(U256::try_from(n - 1_u32).unwrap(), U256::MAX);
+//!
+//! let n = BigInt::from(I256::MIN) * 2;
+//! assert_eq!(I256::try_from(n / 2).unwrap(), I256::MIN);
+//! ```
+
+use crate::{error::tfie, int::I256, uint::U256};
+use ::num_bigint::{BigInt, BigUint, Sign};
+use core::num::TryFromIntError;
+
+impl From<U256> for BigUint {
+    fn from(value: U256) -> Self {
+        BigUint::from_bytes_le(&value.to_le_bytes())
+    }
+}
+
+impl From<U256> for BigInt {
+    fn from(value: U256) -> Self {
+        BigInt::from_biguint(Sign::Plus, value.into())
+    }
+}
+
+impl From<I256> for BigInt {
+    fn from(value: I256) -> Self {
+        BigInt::from_signed_bytes_le(&value.to_le_bytes())
+    }
+}
+
+/// Converts big integer 64-bit digits, ordered from least to most
+/// significant, into a 256-bit unsigned integer.
+fn from_u64_digits(mut digits: impl Iterator<Item = u64>) -> Result<U256, TryFromIntError> {
+    let mut words = [0_u64; 4];
+    for word in &mut words {
+        *word = digits.next().unwrap_or_default();
+    }
+    if digits.next().is_some() {
+        return Err(tfie());
+    }
+
+    let [a, b, c, d] = words.map(u128::from);
+    Ok(U256::from_words((d << 64) | c, (b << 64) | a))
+}
+
+impl TryFrom<&'_ BigUint> for U256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: &BigUint) -> Result<Self, Self::Error> {
+        from_u64_digits(value.iter_u64_digits())
+    }
+}
+
+impl TryFrom<BigUint> for U256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl TryFrom<&'_ BigInt> for U256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        if value.sign() == Sign::Minus {
+            return Err(tfie());
+        }
+        from_u64_digits(value.iter_u64_digits())
+    }
+}
+
+impl TryFrom<BigInt> for U256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl TryFrom<&'_ BigInt> for I256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        let magnitude = from_u64_digits(value.iter_u64_digits())?;
+        if value.sign() == Sign::Minus {
+            if magnitude > I256::MIN.unsigned_abs() {
+                return Err(tfie());
+            }
+            Ok(magnitude.wrapping_neg().as_i256())
+        } else {
+            I256::try_from(magnitude)
+        }
+    }
+}
+
+impl TryFrom<BigInt> for I256 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_conversions() {
+        let big = |value: U256| BigUint::from(value);
+        assert_eq!(big(U256::ZERO), BigUint::default());
+        assert_eq!(big(U256::new(42)), BigUint::from(42_u32));
+        assert_eq!(big(U256::MAX), (BigUint::from(1_u32) << 256_u32) - 1_u32);
+
+        for value in [
+            U256::ZERO,
+            U256::ONE,
+            U256::from_words(0x0123456789abcdef_fedcba9876543210, 42),
+            U256::MAX,
+        ] {
+            assert_eq!(U256::try_from(big(value)), Ok(value));
+            assert_eq!(U256::try_from(BigInt::from(value)), Ok(value));
+        }
+
+        assert!(U256::try_from(BigUint::from(1_u32) << 256_u32).is_err());
+        assert!(U256::try_from(BigInt::from(-1_i32)).is_err());
+    }
+
+    #[test]
+    fn signed_conversions() {
+        let big = |value: I256| BigInt::from(value);
+        assert_eq!(big(I256::MINUS_ONE), BigInt::from(-1_i32));
+        assert_eq!(big(I256::MIN), -(BigInt::from(1_i32) << 255_u32));
+        assert_eq!(big(I256::MAX), (BigInt::from(1_i32) << 255_u32) - 1);
+
+        for value in [
+            I256::MIN,
+            I256::MIN + 1,
+            I256::new(-42),
+            I256::ZERO,
+            I256::new(i128::MAX),
+            I256::MAX,
+        ] {
+            assert_eq!(I256::try_from(big(value)), Ok(value));
+        }
+
+        assert!(I256::try_from(BigInt::from(1_i32) << 255_u32).is_err());
+        assert!(I256::try_from(-(BigInt::from(1_i32) << 255_u32) - 1).is_err());
+        assert!(I256::try_from(-(BigInt::from(1_i32) << 256_u32)).is_err());
+        assert!(I256::try_from(BigInt::from(U256::MAX)).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Implementation of `num-traits` traits for 256-bit integer types.
+//!
+//! This allows [`U256`] and [`I256`] to be used with generic numeric code
+//! written against `num-traits`, in the same way as primitive integer types:
+//!
+//! ```
+//! # use ethnum::U256;
+//! # use num_traits::{CheckedAdd, PrimInt};
+//! fn sum<T: PrimInt>(values: &[T]) -> Option<T> {
+//!     values
+//!         .iter()
+//!         .try_fold(T::zero(), |acc, value| acc.checked_add(value))
+//! }
+//!
+//! assert_eq!(sum(&[U256::ONE, U256::new(41)]), Some(U256::new(42)));
+//! assert_eq!(sum(&[U256::ONE, U256::MAX]), None);
+//! ```
+
+use crate::{int::I256, uint::U256, AsI256, AsU256};
+use ::num_traits::{
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, FromPrimitive,
+    Num, NumCast, One, Pow, PrimInt, Saturating, SaturatingAdd, SaturatingMul, SaturatingSub,
+    Signed, ToPrimitive, Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
+use core::num::ParseIntError;
+
+macro_rules! impl_num_traits {
+    ($($int:ident => $as:ident;)*) => {$(
+        impl Zero for $int {
+            #[inline]
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                *self == Self::ZERO
+            }
+        }
+
+        impl One for $int {
+            #[inline]
+            fn one() -> Self {
+                Self::ONE
+            }
+
+            #[inline]
+            fn is_one(&self) -> bool {
+                *self == Self::ONE
+            }
+        }
+
+        impl Num for $int {
+            type FromStrRadixErr = ParseIntError;
+
+            #[inline]
+            fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                Self::from_str_radix(src, radix)
+            }
+        }
+
+        impl Bounded for $int {
+            #[inline]
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            #[inline]
+            fn max_value() -> Self {
+                Self::MAX
+            }
+        }
+
+        impl PrimInt for $int {
+            #[inline]
+            fn count_ones(self) -> u32 {
+                Self::count_ones(self)
+            }
+
+            #[inline]
+            fn count_zeros(self) -> u32 {
+                Self::count_zeros(self)
+            }
+
+            #[inline]
+            fn leading_ones(self) -> u32 {
+                Self::leading_ones(self)
+            }
+
+            #[inline]
+            fn leading_zeros(self) -> u32 {
+                Self::leading_zeros(self)
+            }
+
+            #[inline]
+            fn trailing_ones(self) -> u32 {
+                Self::trailing_ones(self)
+            }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                Self::trailing_zeros(self)
+            }
+
+            #[inline]
+            fn rotate_left(self, n: u32) -> Self {
+                Self::rotate_left(self, n)
+            }
+
+            #[inline]
+            fn rotate_right(self, n: u32) -> Self {
+                Self::rotate_right(self, n)
+            }
+
+            #[inline]
+            fn signed_shl(self, n: u32) -> Self {
+                (self.as_i256() << n).$as()
+            }
+
+            #[inline]
+            fn signed_shr(self, n: u32) -> Self {
+                (self.as_i256() >> n).$as()
+            }
+
+            #[inline]
+            fn unsigned_shl(self, n: u32) -> Self {
+                (self.as_u256() << n).$as()
+            }
+
+            #[inline]
+            fn unsigned_shr(self, n: u32) -> Self {
+                (self.as_u256() >> n).$as()
+            }
+
+            #[inline]
+            fn swap_bytes(self) -> Self {
+                Self::swap_bytes(self)
+            }
+
+            #[inline]
+            fn reverse_bits(self) -> Self {
+                Self::reverse_bits(self)
+            }
+
+            #[inline]
+            fn from_be(x: Self) -> Self {
+                Self::from_be(x)
+            }
+
+            #[inline]
+            fn from_le(x: Self) -> Self {
+                Self::from_le(x)
+            }
+
+            #[inline]
+            fn to_be(self) -> Self {
+                Self::to_be(self)
+            }
+
+            #[inline]
+            fn to_le(self) -> Self {
+                Self::to_le(self)
+            }
+
+            #[inline]
+            fn pow(self, exp: u32) -> Self {
+                Self::pow(self, exp)
+            }
+        }
+
+        impl Pow<u32> for $int {
+            type Output = Self;
+
+            #[inline]
+            fn pow(self, exp: u32) -> Self {
+                Self::pow(self, exp)
+            }
+        }
+
+        impl Pow<&'_ u32> for $int {
+            type Output = Self;
+
+            #[inline]
+            fn pow(self, exp: &u32) -> Self {
+                Self::pow(self, *exp)
+            }
+        }
+
+        impl_num_traits! {
+            __checked $int {
+                CheckedAdd::checked_add,
+                CheckedSub::checked_sub,
+                CheckedMul::checked_mul,
+                CheckedDiv::checked_div,
+                CheckedRem::checked_rem,
+            }
+        }
+
+        impl CheckedNeg for $int {
+            #[inline]
+            fn checked_neg(&self) -> Option<Self> {
+                Self::checked_neg(*self)
+            }
+        }
+
+        impl_num_traits! {
+            __wrapping $int {
+                WrappingAdd::wrapping_add,
+                WrappingSub::wrapping_sub,
+                WrappingMul::wrapping_mul,
+                SaturatingAdd::saturating_add,
+                SaturatingSub::saturating_sub,
+                SaturatingMul::saturating_mul,
+            }
+        }
+
+        impl Saturating for $int {
+            #[inline]
+            fn saturating_add(self, v: Self) -> Self {
+                Self::saturating_add(self, v)
+            }
+
+            #[inline]
+            fn saturating_sub(self, v: Self) -> Self {
+                Self::saturating_sub(self, v)
+            }
+        }
+
+        impl ToPrimitive for $int {
+            #[inline]
+            fn to_i64(&self) -> Option<i64> {
+                (*self).try_into().ok()
+            }
+
+            #[inline]
+            fn to_i128(&self) -> Option<i128> {
+                (*self).try_into().ok()
+            }
+
+            #[inline]
+            fn to_u64(&self) -> Option<u64> {
+                (*self).try_into().ok()
+            }
+
+            #[inline]
+            fn to_u128(&self) -> Option<u128> {
+                (*self).try_into().ok()
+            }
+
+            #[inline]
+            fn to_f32(&self) -> Option<f32> {
+                Some(self.as_f32())
+            }
+
+            #[inline]
+            fn to_f64(&self) -> Option<f64> {
+                Some(self.as_f64())
+            }
+        }
+
+        impl FromPrimitive for $int {
+            #[inline]
+            fn from_i64(n: i64) -> Option<Self> {
+                Self::from_i128(n.into())
+            }
+
+            #[inline]
+            fn from_i128(n: i128) -> Option<Self> {
+                Self::try_from(I256::new(n)).ok()
+            }
+
+            #[inline]
+            fn from_u64(n: u64) -> Option<Self> {
+                Self::from_u128(n.into())
+            }
+
+            #[inline]
+            fn from_u128(n: u128) -> Option<Self> {
+                Self::try_from(U256::new(n)).ok()
+            }
+
+            #[inline]
+            fn from_f64(n: f64) -> Option<Self> {
+                // Like primitive integers, the fractional part is truncated
+                // and values out of range (including `NaN`) are rejected.
+                // Note that `MAX` rounds up to a power of two, and `MIN` is
+                // either zero or a power of two, so both are exact bounds.
+                let (min, max) = (Self::MIN.as_f64(), Self::MAX.as_f64());
+                if (n > min - 1.0 || n == min) && n < max {
+                    Some(n.$as())
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl NumCast for $int {
+            #[inline]
+            fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+                if let Some(n) = n.to_i128() {
+                    Self::from_i128(n)
+                } else if let Some(n) = n.to_u128() {
+                    Self::from_u128(n)
+                } else {
+                    Self::from_f64(n.to_f64()?)
+                }
+            }
+        }
+    )*};
+
+    (__checked $int:ident { $($trait:ident::$method:ident,)* }) => {$(
+        impl $trait for $int {
+            #[inline]
+            fn $method(&self, v: &Self) -> Option<Self> {
+                Self::$method(*self, *v)
+            }
+        }
+    )*};
+
+    (__wrapping $int:ident { $($trait:ident::$method:ident,)* }) => {$(
+        impl $trait for $int {
+            #[inline]
+            fn $method(&self, v: &Self) -> Self {
+                Self::$method(*self, *v)
+            }
+        }
+    )*};
+}
+
+impl_num_traits! {
+    I256 => as_i256;
+    U256 => as_u256;
+}
+
+impl Unsigned for U256 {}
+
+impl Signed for I256 {
+    #[inline]
+    fn abs(&self) -> Self {
+        Self::abs(*self)
+    }
+
+    #[inline]
+    fn abs_sub(&self, other: &Self) -> Self {
+        if *self <= *other {
+            Self::ZERO
+        } else {
+            *self - *other
+        }
+    }
+
+    #[inline]
+    fn signum(&self) -> Self {
+        Self::signum(*self)
+    }
+
+    #[inline]
+    fn is_positive(&self) -> bool {
+        Self::is_positive(*self)
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        Self::is_negative(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primint<T: PrimInt>() {}
+
+    #[test]
+    fn trait_implementations() {
+        primint::<U256>();
+        primint::<I256>();
+    }
+
+    #[test]
+    fn shifts() {
+        let x = U256::MAX - 1;
+        assert_eq!(x.signed_shr(4), U256::MAX);
+        assert_eq!(x.unsigned_shr(4), U256::MAX >> 4);
+        assert_eq!(x.signed_shl(4), x << 4);
+
+        let y = I256::new(-2);
+        assert_eq!(y.signed_shr(4), -1);
+        assert_eq!(y.unsigned_shr(4), ((U256::MAX - 1) >> 4u32).as_i256());
+        assert_eq!(y.unsigned_shl(4), -32);
+    }
+
+    #[test]
+    fn primitive_conversions() {
+        assert_eq!(U256::from_i64(-1), None);
+        assert_eq!(U256::from_u128(u128::MAX), Some(U256::new(u128::MAX)));
+        assert_eq!(I256::from_i128(i128::MIN), Some(I256::new(i128::MIN)));
+
+        assert_eq!(U256::from_f64(-0.5), Some(U256::ZERO));
+        assert_eq!(U256::from_f64(-1.0), None);
+        assert_eq!(U256::from_f64(42.9), Some(U256::new(42)));
+        assert_eq!(U256::from_f64(2.0f64.powi(256)), None);
+        assert_eq!(I256::from_f64(-(2.0f64.powi(255))), Some(I256::MIN));
+        assert_eq!(I256::from_f64(2.0f64.powi(255)), None);
+        assert_eq!(I256::from_f64(f64::NAN), None);
+
+        assert_eq!(U256::MAX.to_u128(), None);
+        assert_eq!(I256::MINUS_ONE.to_u64(), None);
+        assert_eq!(I256::MINUS_ONE.to_i64(), Some(-1));
+        assert_eq!(U256::ONE.to_f64(), Some(1.0));
+    }
+
+    #[test]
+    fn num_cast() {
+        assert_eq!(<U256 as NumCast>::from(42_u8), Some(U256::new(42)));
+        assert_eq!(<U256 as NumCast>::from(-1_i8), None);
+        assert_eq!(
+            <I256 as NumCast>::from(u128::MAX),
+            Some(u128::MAX.as_i256())
+        );
+        assert_eq!(<I256 as NumCast>::from(U256::MAX), None);
+        assert_eq!(<U256 as NumCast>::from(I256::MIN), None);
+        assert_eq!(<U256 as NumCast>::from(1e30_f64), U256::from_f64(1e30));
+        assert_eq!(<u64 as NumCast>::from(U256::new(7)), Some(7));
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(Signed::abs(&I256::new(-3)), 3);
+        assert_eq!(I256::new(5).abs_sub(&I256::new(7)), 0);
+        assert_eq!(I256::new(7).abs_sub(&I256::new(5)), 2);
+        assert_eq!(Signed::signum(&I256::new(-3)), -1);
+    }
+
+    #[test]
+    fn checked_and_saturating() {
+        assert_eq!(CheckedAdd::checked_add(&U256::MAX, &U256::ONE), None);
+        assert_eq!(CheckedDiv::checked_div(&I256::MIN, &I256::MINUS_ONE), None);
+        assert_eq!(CheckedNeg::checked_neg(&U256::ONE), None);
+        assert_eq!(WrappingAdd::wrapping_add(&U256::MAX, &U256::ONE), 0);
+        assert_eq!(
+            SaturatingMul::saturating_mul(&I256::MIN, &I256::new(2)),
+            I256::MIN
+        );
+        assert_eq!(Saturating::saturating_sub(U256::ZERO, U256::ONE), 0);
+        assert_eq!(Pow::pow(U256::new(2), 255), U256::ONE << 255);
+    }
+}
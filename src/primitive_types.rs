@@ -0,0 +1,49 @@
+//! Conversions between 256-bit unsigned integers and `primitive-types`.
+//!
+//! Both types store 256-bit unsigned integers as little endian 64-bit limbs
+//! (on little endian targets), so conversions are simple reinterpretations
+//! that do not go through byte arrays:
+//!
+//! ```
+//! # use ethnum::U256;
+//! let value = primitive_types::U256::from(42);
+//! assert_eq!(U256::from(value), 42);
+//! assert_eq!(primitive_types::U256::from(U256::MAX), primitive_types::U256::MAX);
+//! ```
+
+use crate::uint::U256;
+
+impl From<::primitive_types::U256> for U256 {
+    #[inline]
+    fn from(value: ::primitive_types::U256) -> Self {
+        U256::from_limbs(value.0)
+    }
+}
+
+impl From<U256> for ::primitive_types::U256 {
+    #[inline]
+    fn from(value: U256) -> Self {
+        ::primitive_types::U256(value.into_limbs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            U256::ZERO,
+            U256::ONE,
+            U256::from_words(0x0123456789abcdef_fedcba9876543210, 42),
+            U256::MAX,
+        ] {
+            let converted = ::primitive_types::U256::from(value);
+            let mut bytes = [0; 32];
+            converted.to_big_endian(&mut bytes);
+            assert_eq!(bytes, value.to_be_bytes());
+            assert_eq!(U256::from(converted), value);
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Proptest strategies for 256-bit integer types.
+//!
+//! This module implements `proptest::arbitrary::Arbitrary` for [`U256`] and
+//! [`I256`], so that `any::<U256>()` can be used in property tests. Arbitrary
+//! values are mostly uniformly distributed, but are mixed with
+//! [`interesting`] values that tend to trigger edge cases in 256-bit
+//! arithmetic. Additionally, [`range`] can be used to generate values in a
+//! range:
+//!
+//! ```
+//! # use ethnum::{proptest::range, U256};
+//! # use proptest::prelude::*;
+//! proptest! {
+//!     fn in_range(x in range(U256::new(1000)..U256::new(2000))) {
+//!         prop_assert!(x >= 1000 && x < 2000);
+//!     }
+//! }
+//! # in_range();
+//! ```
+//!
+//! All strategies shrink towards zero, or the bound closest to zero for
+//! ranges that do not contain it.
+
+use crate::{int::I256, uint::U256};
+use ::proptest::{
+    arbitrary::Arbitrary,
+    num,
+    strategy::{NewTree, Strategy, ValueTree},
+    test_runner::{Reason, TestRunner},
+};
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::I256 {}
+    impl Sealed for super::U256 {}
+}
+
+/// A 256-bit integer type that can be generated by the strategies in this
+/// module.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Int256: Copy + Debug + Ord + private::Sealed {
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const MIN: Self;
+    #[doc(hidden)]
+    const MAX: Self;
+    #[doc(hidden)]
+    fn from_bits(bits: U256) -> Self;
+    #[doc(hidden)]
+    fn into_bits(self) -> U256;
+}
+
+impl Int256 for I256 {
+    const ZERO: Self = I256::ZERO;
+    const MIN: Self = I256::MIN;
+    const MAX: Self = I256::MAX;
+    fn from_bits(bits: U256) -> Self {
+        bits.as_i256()
+    }
+    fn into_bits(self) -> U256 {
+        self.as_u256()
+    }
+}
+
+impl Int256 for U256 {
+    const ZERO: Self = U256::ZERO;
+    const MIN: Self = U256::MIN;
+    const MAX: Self = U256::MAX;
+    fn from_bits(bits: U256) -> Self {
+        bits
+    }
+    fn into_bits(self) -> U256 {
+        self
+    }
+}
+
+/// Generates a uniformly distributed random 32-bit value.
+///
+/// Randomness is drawn through `proptest`'s own primitive integer strategies
+/// so that we do not depend on the `rand` version it uses.
+fn random_u32(runner: &mut TestRunner) -> Result<u32, Reason> {
+    Ok(num::u32::ANY.new_tree(runner)?.current())
+}
+
+/// Generates a uniformly distributed random 256-bit value.
+fn random(runner: &mut TestRunner) -> Result<U256, Reason> {
+    let hi = num::u128::ANY.new_tree(runner)?.current();
+    let lo = num::u128::ANY.new_tree(runner)?.current();
+    Ok(U256::from_words(hi, lo))
+}
+
+/// Generates a uniformly distributed value in the inclusive range `0..=span`
+/// without bias, by rejecting masked random values outside of the range.
+fn random_span(runner: &mut TestRunner, span: U256) -> Result<U256, Reason> {
+    if span == 0 {
+        return Ok(U256::ZERO);
+    }
+
+    let mask = U256::MAX >> span.leading_zeros();
+    loop {
+        let value = random(runner)? & mask;
+        if value <= span {
+            return Ok(value);
+        }
+    }
+}
+
+/// Generates an interesting value. These are `0`, `MIN`, `MAX`, as well as
+/// powers of two and their immediate neighbours (including the `2^128` word
+/// boundary), and their negation for signed integers.
+fn random_interesting<T: Int256>(runner: &mut TestRunner) -> Result<T, Reason> {
+    let choice = random_u32(runner)?;
+    let bits = match choice % 8 {
+        0 => U256::ZERO,
+        1 => T::MIN.into_bits(),
+        2 => T::MAX.into_bits(),
+        _ => {
+            let power = U256::ONE << ((choice >> 8) % 256);
+            match (choice >> 16) % 3 {
+                0 => power.wrapping_sub(U256::ONE),
+                1 => power,
+                _ => power.wrapping_add(U256::ONE),
+            }
+        }
+    };
+
+    let signed = T::MIN < T::ZERO;
+    if signed && (choice >> 24) & 1 == 1 {
+        Ok(T::from_bits(bits.wrapping_neg()))
+    } else {
+        Ok(T::from_bits(bits))
+    }
+}
+
+/// Value tree that shrinks a value towards a target by binary searching over
+/// its distance from that target.
+#[derive(Clone, Copy, Debug)]
+pub struct BinarySearch<T> {
+    target: T,
+    negative: bool,
+    lo: U256,
+    curr: U256,
+    hi: U256,
+}
+
+impl<T: Int256> BinarySearch<T> {
+    /// Creates a new binary searcher starting at `value` and shrinking
+    /// towards `target`.
+    pub fn new(target: T, value: T) -> Self {
+        let (negative, distance) = if value < target {
+            (true, target.into_bits().wrapping_sub(value.into_bits()))
+        } else {
+            (false, value.into_bits().wrapping_sub(target.into_bits()))
+        };
+        Self {
+            target,
+            negative,
+            lo: U256::ZERO,
+            curr: distance,
+            hi: distance,
+        }
+    }
+
+    fn reposition(&mut self) -> bool {
+        let mid = self.lo + (self.hi - self.lo) / 2;
+        if mid == self.curr {
+            false
+        } else {
+            self.curr = mid;
+            true
+        }
+    }
+}
+
+impl<T: Int256> ValueTree for BinarySearch<T> {
+    type Value = T;
+
+    fn current(&self) -> T {
+        let target = self.target.into_bits();
+        T::from_bits(if self.negative {
+            target.wrapping_sub(self.curr)
+        } else {
+            target.wrapping_add(self.curr)
+        })
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.hi <= self.lo {
+            return false;
+        }
+        self.hi = self.curr;
+        self.reposition()
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.hi <= self.lo {
+            return false;
+        }
+        self.lo = self.curr + 1;
+        self.reposition()
+    }
+}
+
+/// Strategy generating arbitrary 256-bit integers, returned by
+/// `any::<U256>()` and `any::<I256>()`.
+///
+/// Values are uniformly distributed, except for one in four values which are
+/// [`interesting`] ones.
+#[derive(Clone, Copy, Debug)]
+pub struct Any<T>(PhantomData<T>);
+
+impl<T: Int256> Strategy for Any<T> {
+    type Tree = BinarySearch<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let value = if random_u32(runner)? % 4 == 0 {
+            random_interesting(runner)?
+        } else {
+            T::from_bits(random(runner)?)
+        };
+        Ok(BinarySearch::new(T::ZERO, value))
+    }
+}
+
+macro_rules! impl_arbitrary {
+    ($($int:ident),*) => {$(
+        impl Arbitrary for $int {
+            type Parameters = ();
+            type Strategy = Any<$int>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                Any(PhantomData)
+            }
+        }
+    )*};
+}
+
+impl_arbitrary! { I256, U256 }
+
+/// Strategy generating interesting 256-bit integers, returned by
+/// [`interesting`].
+#[derive(Clone, Copy, Debug)]
+pub struct Interesting<T>(PhantomData<T>);
+
+impl<T: Int256> Strategy for Interesting<T> {
+    type Tree = BinarySearch<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(BinarySearch::new(T::ZERO, random_interesting(runner)?))
+    }
+}
+
+/// Returns a strategy generating values that tend to trigger edge cases:
+/// zero, `MIN` and `MAX`, as well as powers of two and their immediate
+/// neighbours (`2^n - 1`, `2^n` and `2^n + 1`), and their negation for signed
+/// integers.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{proptest::interesting, U256};
+/// # use proptest::prelude::*;
+/// proptest! {
+///     fn wrapping_neg(x in interesting::<U256>()) {
+///         prop_assert_eq!(x.wrapping_neg().wrapping_add(x), 0);
+///     }
+/// }
+/// # wrapping_neg();
+/// ```
+pub fn interesting<T: Int256>() -> Interesting<T> {
+    Interesting(PhantomData)
+}
+
+/// Strategy generating 256-bit integers in a range, returned by [`range`].
+#[derive(Clone, Copy, Debug)]
+pub struct Range<T> {
+    start: T,
+    span: U256,
+}
+
+impl<T: Int256> Strategy for Range<T> {
+    type Tree = BinarySearch<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let offset = random_span(runner, self.span)?;
+        let value = T::from_bits(self.start.into_bits().wrapping_add(offset));
+        let end = T::from_bits(self.start.into_bits().wrapping_add(self.span));
+        let target = T::ZERO.clamp(self.start, end);
+        Ok(BinarySearch::new(target, value))
+    }
+}
+
+/// Returns a strategy generating uniformly distributed values in the
+/// specified range. Generated values shrink towards zero if it is contained
+/// in the range, or to the range bound closest to zero otherwise.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+pub fn range<T: Int256>(bounds: impl RangeBounds<T>) -> Range<T> {
+    let start = match bounds.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => {
+            (start != T::MAX).then(|| T::from_bits(start.into_bits().wrapping_add(U256::ONE)))
+        }
+        Bound::Unbounded => Some(T::MIN),
+    };
+    let end = match bounds.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => {
+            (end != T::MIN).then(|| T::from_bits(end.into_bits().wrapping_sub(U256::ONE)))
+        }
+        Bound::Unbounded => Some(T::MAX),
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => Range {
+            start,
+            span: end.into_bits().wrapping_sub(start.into_bits()),
+        },
+        _ => panic!("range strategy called with an empty range"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::proptest::{prelude::*, test_runner::Config};
+
+    /// Finds the minimal value generated by a strategy for which `fails`
+    /// holds.
+    fn minimal<S>(strategy: S, fails: impl Fn(S::Value) -> bool) -> S::Value
+    where
+        S: Strategy,
+        S::Value: Copy,
+    {
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            ..Config::default()
+        });
+        let mut tree = loop {
+            let tree = strategy.new_tree(&mut runner).unwrap();
+            if fails(tree.current()) {
+                break tree;
+            }
+        };
+
+        let mut minimal = tree.current();
+        loop {
+            let value = tree.current();
+            let shrunk = if fails(value) {
+                minimal = value;
+                tree.simplify()
+            } else {
+                tree.complicate()
+            };
+            if !shrunk {
+                return minimal;
+            }
+        }
+    }
+
+    #[test]
+    fn shrinks_towards_zero() {
+        assert_eq!(minimal(any::<U256>(), |x| x >= 1000), 1000);
+        assert_eq!(minimal(any::<I256>(), |x| x <= -1000), -1000);
+        assert_eq!(
+            minimal(interesting::<U256>(), |x| x > U256::ONE << 128),
+            (U256::ONE << 128) + U256::ONE
+        );
+    }
+
+    #[test]
+    fn shrinks_towards_range_bound() {
+        let low = U256::from_words(1, 0);
+        assert_eq!(minimal(range(low..), |_| true), low);
+        assert_eq!(minimal(range(..I256::new(-5)), |_| true), -6);
+        assert_eq!(
+            minimal(range(I256::new(-5)..=I256::new(5)), |x| x <= -2),
+            -2
+        );
+    }
+
+    #[test]
+    fn interesting_values() {
+        let mut runner = TestRunner::deterministic();
+        let values = (0..20_000)
+            .map(|_| random_interesting::<I256>(&mut runner).unwrap())
+            .collect::<alloc::vec::Vec<_>>();
+        for expected in [
+            I256::ZERO,
+            I256::MIN,
+            I256::MAX,
+            I256::ONE << 128,
+            (I256::ONE << 128) - I256::ONE,
+            I256::MINUS_ONE - (I256::ONE << 128),
+        ] {
+            assert!(values.contains(&expected), "{expected}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_range() {
+        range(U256::MAX..U256::MAX);
+    }
+
+    proptest! {
+        #[test]
+        fn ranges_are_respected(
+            x in range(U256::from_words(1, 0)..U256::from_words(1, 1000)),
+            y in range(I256::MIN..=I256::new(-1)),
+            z in range(..),
+        ) {
+            prop_assert!(x >= U256::from_words(1, 0) && x < U256::from_words(1, 1000));
+            prop_assert!(y < 0);
+            let _: I256 = z;
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Random number generation for 256-bit integer types.
+//!
+//! This module implements sampling from the [`Standard`] distribution, which
+//! produces integers uniformly distributed over all possible values, as well
+//! as uniform sampling over arbitrary ranges:
+//!
+//! ```
+//! # use ethnum::U256;
+//! # use rand::Rng;
+//! let mut rng = rand::rngs::mock::StepRng::new(42, 1);
+//! let value = rng.gen_range(U256::new(1000)..U256::new(2000));
+//! assert!(value >= 1000 && value < 2000);
+//! ```
+//!
+//! Range sampling is unbiased. Random values are masked to the bit length of
+//! the range and rejected if they fall outside of it, so that each sample is
+//! accepted with probability greater than one half.
+
+use crate::{int::I256, uint::U256, AsU256 as _};
+use ::rand::{
+    distributions::{
+        uniform::{SampleBorrow, SampleUniform, UniformSampler},
+        Distribution, Standard,
+    },
+    Rng,
+};
+
+impl Distribution<U256> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> U256 {
+        U256::from_words(rng.gen(), rng.gen())
+    }
+}
+
+impl Distribution<I256> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> I256 {
+        I256::from_words(rng.gen(), rng.gen())
+    }
+}
+
+/// Samples a value uniformly distributed in the inclusive range `0..=span`.
+#[inline]
+fn sample_span<R: Rng + ?Sized>(rng: &mut R, span: U256) -> U256 {
+    if span == 0 {
+        return U256::ZERO;
+    }
+
+    let mask = U256::MAX >> span.leading_zeros();
+    loop {
+        let value = rng.gen::<U256>() & mask;
+        if value <= span {
+            return value;
+        }
+    }
+}
+
+macro_rules! impl_uniform {
+    ($($(#[$attr:meta])* $sampler:ident => $int:ident [$as:ident],)*) => {$(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $sampler {
+            low: $int,
+            span: U256,
+        }
+
+        impl UniformSampler for $sampler {
+            type X = $int;
+
+            #[inline]
+            fn new<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(low < high, "Uniform::new called with `low >= high`");
+                Self::new_inclusive(low, high - 1)
+            }
+
+            #[inline]
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+                Self {
+                    low,
+                    span: high.wrapping_sub(low).as_u256(),
+                }
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                self.low.wrapping_add(sample_span(rng, self.span).$as())
+            }
+        }
+
+        impl SampleUniform for $int {
+            type Sampler = $sampler;
+        }
+    )*};
+}
+
+impl_uniform! {
+    /// The back-end implementing uniform sampling of [`I256`] values over a
+    /// range.
+    UniformI256 => I256 [as_i256],
+    /// The back-end implementing uniform sampling of [`U256`] values over a
+    /// range.
+    UniformU256 => U256 [as_u256],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::{distributions::Uniform, rngs::SmallRng, SeedableRng};
+
+    fn rng() -> SmallRng {
+        SmallRng::seed_from_u64(0x5eed)
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut rng = rng();
+        let (low, high) = (U256::from_words(1, 0), U256::from_words(1, 1000));
+        for _ in 0..1000 {
+            let value = rng.gen_range(low..high);
+            assert!(value >= low && value < high);
+        }
+
+        let (low, high) = (I256::new(-500), I256::new(500));
+        for _ in 0..1000 {
+            let value = rng.gen_range(low..=high);
+            assert!(value >= low && value <= high);
+        }
+
+        assert_eq!(rng.gen_range(U256::MAX..=U256::MAX), U256::MAX);
+        assert_eq!(rng.gen_range(I256::MIN..I256::MIN + 1), I256::MIN);
+    }
+
+    #[test]
+    fn full_ranges() {
+        let mut rng = rng();
+        let unsigned = Uniform::new_inclusive(U256::ZERO, U256::MAX);
+        let signed = Uniform::new_inclusive(I256::MIN, I256::MAX);
+        assert!((0..64).any(|_| unsigned.sample(&mut rng) > U256::MAX / 2));
+        assert!((0..64).any(|_| signed.sample(&mut rng) < 0));
+    }
+
+    #[test]
+    fn uniform_distribution() {
+        // A range length just above a power of two, so that close to half of
+        // the masked samples get rejected.
+        let mut rng = rng();
+        let low = I256::new(-3);
+        let mut counts = [0_u32; 5];
+        for _ in 0..50_000 {
+            let value = rng.gen_range(low..low + 5);
+            counts[(value - low).as_usize()] += 1;
+        }
+        for count in counts {
+            assert!((9_000..11_000).contains(&count), "{counts:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_range() {
+        rng().gen_range(U256::ONE..U256::ONE);
+    }
+}
@@ -0,0 +1,51 @@
+//! Conversions between 256-bit unsigned integers and `ruint`.
+//!
+//! This also covers `alloy_primitives::U256`, which is an alias for
+//! `ruint::Uint<256, 4>`. Both types store 256-bit unsigned integers as little
+//! endian 64-bit limbs (on little endian targets), so conversions are simple
+//! reinterpretations that do not go through byte arrays:
+//!
+//! ```
+//! # use ethnum::U256;
+//! # use ruint::Uint;
+//! let value = Uint::<256, 4>::from(42);
+//! assert_eq!(U256::from(value), 42);
+//! let value: Uint<256, 4> = U256::MAX.into();
+//! assert_eq!(value, Uint::MAX);
+//! ```
+
+use crate::uint::U256;
+use ::ruint::Uint;
+
+impl From<Uint<256, 4>> for U256 {
+    #[inline]
+    fn from(value: Uint<256, 4>) -> Self {
+        U256::from_limbs(value.into_limbs())
+    }
+}
+
+impl From<U256> for Uint<256, 4> {
+    #[inline]
+    fn from(value: U256) -> Self {
+        Uint::from_limbs(value.into_limbs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            U256::ZERO,
+            U256::ONE,
+            U256::from_words(0x0123456789abcdef_fedcba9876543210, 42),
+            U256::MAX,
+        ] {
+            let converted: Uint<256, 4> = value.into();
+            assert_eq!(converted.to_be_bytes::<32>(), value.to_be_bytes());
+            assert_eq!(U256::from(converted), value);
+        }
+    }
+}
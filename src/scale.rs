@@ -0,0 +1,279 @@
+//! SCALE codec implementation for 256-bit integer types.
+//!
+//! Integers are encoded as fixed-size 32-byte little endian values, matching
+//! the encoding used for `U256` balances on Substrate-based chains.
+//!
+//! Note that, because of coherence rules, `Compact<U256>` cannot be
+//! implemented outside of the `parity-scale-codec` crate. Instead, this module
+//! provides the [`CompactU256`] type which implements the SCALE compact
+//! encoding using the variable-length big-integer mode. It can be used either
+//! directly, or with the `#[codec(encoded_as = "...")]` attribute:
+//!
+//! ```
+//! # use ethnum::{scale::CompactU256, U256};
+//! # use parity_scale_codec::{Decode, Encode};
+//! let encoded = CompactU256(U256::new(42)).encode();
+//! assert_eq!(encoded, [42 << 2]);
+//! assert_eq!(CompactU256::decode(&mut &encoded[..]).unwrap().0, 42);
+//! ```
+
+use crate::{int::I256, uint::U256};
+use parity_scale_codec::{
+    ConstEncodedLen, Decode, Encode, EncodeAsRef, EncodeLike, Error, Input, MaxEncodedLen, Output,
+};
+
+/// The number of bytes in a fixed-size SCALE encoded 256-bit integer.
+const BYTES_LEN: usize = 32;
+
+macro_rules! impl_scale {
+    ($($int:ident),*) => {$(
+        impl Encode for $int {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                BYTES_LEN
+            }
+
+            #[inline]
+            fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+                dest.write(&self.to_le_bytes());
+            }
+
+            #[inline]
+            fn encoded_size(&self) -> usize {
+                BYTES_LEN
+            }
+        }
+
+        impl EncodeLike for $int {}
+
+        impl Decode for $int {
+            #[inline]
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                let mut bytes = [0; BYTES_LEN];
+                input.read(&mut bytes)?;
+                Ok(Self::from_le_bytes(bytes))
+            }
+
+            #[inline]
+            fn encoded_fixed_size() -> Option<usize> {
+                Some(BYTES_LEN)
+            }
+        }
+
+        impl MaxEncodedLen for $int {
+            #[inline]
+            fn max_encoded_len() -> usize {
+                BYTES_LEN
+            }
+        }
+
+        impl ConstEncodedLen for $int {}
+    )*};
+}
+
+impl_scale! { I256, U256 }
+
+/// A 256-bit unsigned integer with SCALE compact encoding.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CompactU256(pub U256);
+
+const OUT_OF_RANGE: &str = "out of range decoding Compact<U256>";
+
+impl From<U256> for CompactU256 {
+    #[inline]
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&'_ U256> for CompactU256 {
+    #[inline]
+    fn from(value: &U256) -> Self {
+        Self(*value)
+    }
+}
+
+impl From<CompactU256> for U256 {
+    #[inline]
+    fn from(value: CompactU256) -> Self {
+        value.0
+    }
+}
+
+impl<'a> EncodeAsRef<'a, U256> for CompactU256 {
+    type RefType = CompactU256;
+}
+
+impl CompactU256 {
+    /// Returns the number of significant bytes for a value requiring the
+    /// big-integer mode.
+    #[inline]
+    fn big_len(&self) -> usize {
+        (U256::BITS - self.0.leading_zeros()).div_ceil(8) as _
+    }
+}
+
+impl Encode for CompactU256 {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.encoded_size()
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self.0.into_words() {
+            (0, x @ 0..=0x3f) => dest.push_byte((x as u8) << 2),
+            (0, x @ 0..=0x3fff) => dest.write(&(((x as u16) << 2) | 0b01).to_le_bytes()),
+            (0, x @ 0..=0x3fff_ffff) => dest.write(&(((x as u32) << 2) | 0b10).to_le_bytes()),
+            _ => {
+                let len = self.big_len();
+                dest.push_byte((((len - 4) as u8) << 2) | 0b11);
+                dest.write(&self.0.to_le_bytes()[..len]);
+            }
+        }
+    }
+
+    fn encoded_size(&self) -> usize {
+        match self.0.into_words() {
+            (0, 0..=0x3f) => 1,
+            (0, 0..=0x3fff) => 2,
+            (0, 0..=0x3fff_ffff) => 4,
+            _ => 1 + self.big_len(),
+        }
+    }
+}
+
+impl EncodeLike for CompactU256 {}
+impl EncodeLike<U256> for CompactU256 {}
+
+impl Decode for CompactU256 {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let prefix = input.read_byte()?;
+        let value = match prefix & 0b11 {
+            0b00 => U256::from(prefix >> 2),
+            0b01 => {
+                let mut bytes = [prefix, 0];
+                input.read(&mut bytes[1..])?;
+                let x = u16::from_le_bytes(bytes) >> 2;
+                if x <= 0x3f {
+                    return Err(OUT_OF_RANGE.into());
+                }
+                U256::from(x)
+            }
+            0b10 => {
+                let mut bytes = [prefix, 0, 0, 0];
+                input.read(&mut bytes[1..])?;
+                let x = u32::from_le_bytes(bytes) >> 2;
+                if x <= 0x3fff {
+                    return Err(OUT_OF_RANGE.into());
+                }
+                U256::from(x)
+            }
+            _ => {
+                let len = (prefix >> 2) as usize + 4;
+                if len > BYTES_LEN {
+                    return Err(OUT_OF_RANGE.into());
+                }
+                let mut bytes = [0; BYTES_LEN];
+                input.read(&mut bytes[..len])?;
+
+                // Only accept canonical encodings, that is values that could
+                // not have been encoded with a shorter length.
+                let canonical = match len {
+                    4 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) > 0x3fff_ffff,
+                    _ => bytes[len - 1] != 0,
+                };
+                if !canonical {
+                    return Err(OUT_OF_RANGE.into());
+                }
+                U256::from_le_bytes(bytes)
+            }
+        };
+        Ok(Self(value))
+    }
+}
+
+impl MaxEncodedLen for CompactU256 {
+    #[inline]
+    fn max_encoded_len() -> usize {
+        1 + BYTES_LEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Compact;
+
+    #[test]
+    fn fixed_size() {
+        for value in [U256::ZERO, U256::new(42), U256::MAX] {
+            let encoded = value.encode();
+            assert_eq!(encoded, value.to_le_bytes());
+            assert_eq!(U256::decode(&mut &encoded[..]).unwrap(), value);
+        }
+        for value in [I256::MIN, I256::MINUS_ONE, I256::MAX] {
+            let encoded = value.encode();
+            assert_eq!(encoded, value.to_le_bytes());
+            assert_eq!(I256::decode(&mut &encoded[..]).unwrap(), value);
+        }
+
+        assert!(U256::decode(&mut &[0; 31][..]).is_err());
+        assert_eq!(U256::max_encoded_len(), 32);
+        assert_eq!(I256::max_encoded_len(), 32);
+    }
+
+    #[test]
+    fn compact_matches_primitives() {
+        for x in [
+            0,
+            1,
+            0x3f,
+            0x40,
+            0x3fff,
+            0x4000,
+            0x3fff_ffff,
+            0x4000_0000,
+            u32::MAX as u128,
+            u64::MAX as u128,
+            1 << 64,
+            u128::MAX,
+        ] {
+            let encoded = CompactU256(U256::new(x)).encode();
+            assert_eq!(encoded, Compact(x).encode());
+            assert_eq!(encoded.len(), CompactU256(U256::new(x)).encoded_size());
+            assert_eq!(
+                CompactU256::decode(&mut &encoded[..]).unwrap(),
+                CompactU256(U256::new(x)),
+            );
+        }
+    }
+
+    #[test]
+    fn compact_big_integers() {
+        let encoded = CompactU256(U256::MAX).encode();
+        assert_eq!(encoded.len(), CompactU256::max_encoded_len());
+        assert_eq!(encoded[0], (28 << 2) | 0b11);
+        assert_eq!(
+            CompactU256::decode(&mut &encoded[..]).unwrap(),
+            CompactU256(U256::MAX),
+        );
+
+        let value = U256::ONE << 128;
+        let encoded = CompactU256(value).encode();
+        assert_eq!(encoded.len(), 18);
+        assert_eq!(CompactU256::decode(&mut &encoded[..]).unwrap().0, value);
+    }
+
+    #[test]
+    fn compact_rejects_non_canonical() {
+        for encoded in [
+            &[0b01, 0][..],
+            &[0b10, 0, 0, 0],
+            &[0b11, 0xff, 0xff, 0xff, 0x3f],
+            &[0b111, 1, 0, 0, 0, 0],
+            &[(29 << 2) | 0b11],
+        ] {
+            assert!(CompactU256::decode(&mut &encoded[..]).is_err());
+        }
+    }
+}
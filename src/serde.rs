@@ -1,23 +1,28 @@
 //! Serde serialization implementation for 256-bit integer types.
 //!
-//! This implementation is very JSON-centric in that it serializes the integer
-//! types as `QUANTITIES` as specified in the Ethereum RPC. That is, integers
-//! are encoded as `"0x"` prefixed strings without extrenuous leading `0`s. For
-//! negative signed integers, the string is prefixed with a `"-"` sign.
+//! For human readable formats, this implementation is very JSON-centric in
+//! that it serializes the integer types as `QUANTITIES` as specified in the
+//! Ethereum RPC. That is, integers are encoded as `"0x"` prefixed strings
+//! without extrenuous leading `0`s. For negative signed integers, the string is
+//! prefixed with a `"-"` sign.
+//!
+//! For binary formats (i.e. formats that are not human readable such as
+//! `bincode`), integers are encoded as fixed 32-byte big endian byte strings.
+//! Use the [`quantity`] module for `QUANTITY` strings regardless of format.
 //!
 //! Note that this module contains alternative serialization schemes that can
 //! be used with `#[serde(with = "...")]`.
 //!
 //! TODO(nlordell): example!
 
-use crate::{int::I256, uint::U256};
+use crate::{int::I256, uint::U256, FormatBuffer};
 use core::{
+    convert::TryInto,
     fmt::{self, Display, Formatter, Write},
-    mem::MaybeUninit,
-    ptr, slice, str,
+    str,
 };
 use serde::{
-    de::{self, Visitor},
+    de::{self, SeqAccess, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
@@ -26,9 +31,11 @@ impl Serialize for I256 {
     where
         S: Serializer,
     {
-        let mut f = FormatBuffer::hex();
-        write!(f, "{self:-#x}").expect("unexpected formatting failure");
-        serializer.serialize_str(f.as_str())
+        if serializer.is_human_readable() {
+            quantity::serialize(self, serializer)
+        } else {
+            serializer.serialize_bytes(&self.to_be_bytes())
+        }
     }
 }
 
@@ -37,9 +44,11 @@ impl Serialize for U256 {
     where
         S: Serializer,
     {
-        let mut f = FormatBuffer::hex();
-        write!(f, "{self:#x}").expect("unexpected formatting failure");
-        serializer.serialize_str(f.as_str())
+        if serializer.is_human_readable() {
+            quantity::serialize(self, serializer)
+        } else {
+            serializer.serialize_bytes(&self.to_be_bytes())
+        }
     }
 }
 
@@ -48,7 +57,11 @@ impl<'de> Deserialize<'de> for I256 {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(FormatVisitor(Self::from_str_hex))
+        if deserializer.is_human_readable() {
+            quantity::deserialize(deserializer)
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor(Self::from_be_bytes))
+        }
     }
 }
 
@@ -57,7 +70,94 @@ impl<'de> Deserialize<'de> for U256 {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(FormatVisitor(Self::from_str_hex))
+        if deserializer.is_human_readable() {
+            quantity::deserialize(deserializer)
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor(Self::from_be_bytes))
+        }
+    }
+}
+
+/// Module for use with `#[serde(with = "ethnum::serde::quantity")]` to specify
+/// `QUANTITY` string serialization for 256-bit integer types.
+///
+/// This is the default serialization for human readable formats, and this
+/// module can be used to preserve it for binary formats as well.
+pub mod quantity {
+    use super::*;
+    use crate::ParseError;
+
+    #[doc(hidden)]
+    pub trait Quantity: Sized {
+        fn from_str_hex(src: &str) -> Result<Self, ParseError>;
+        fn write_hex(&self, f: &mut impl Write);
+    }
+
+    impl Quantity for I256 {
+        fn from_str_hex(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_hex_detailed(src)
+        }
+        fn write_hex(&self, f: &mut impl Write) {
+            write!(f, "{self:-#x}").expect("unexpected formatting failure")
+        }
+    }
+
+    impl Quantity for U256 {
+        fn from_str_hex(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_hex_detailed(src)
+        }
+        fn write_hex(&self, f: &mut impl Write) {
+            write!(f, "{self:#x}").expect("unexpected formatting failure")
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Quantity,
+        S: Serializer,
+    {
+        let mut f = hex_buffer();
+        value.write_hex(&mut f);
+        serializer.serialize_str(f.as_str())
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Quantity,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FormatVisitor(T::from_str_hex))
+    }
+}
+
+/// Module for use with `#[serde(with = "ethnum::serde::quantity_strict")]` to
+/// specify strict `QUANTITY` string serialization for unsigned 256-bit
+/// integers.
+///
+/// Serialization is identical to the [`quantity`] module. Deserialization,
+/// however, strictly follows the Ethereum JSON-RPC specification and rejects
+/// inputs that [`U256::from_str_hex`] would accept, such as leading zeros,
+/// empty `0x` strings, signs, and upper-case prefixes. See
+/// [`U256::from_quantity_str`] for more details.
+pub mod quantity_strict {
+    use super::*;
+
+    #[doc(hidden)]
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        quantity::serialize(value, serializer)
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FormatVisitor(U256::from_quantity_str))
     }
 }
 
@@ -65,17 +165,17 @@ impl<'de> Deserialize<'de> for U256 {
 /// decimal string serialization for 256-bit integer types.
 pub mod decimal {
     use super::*;
-    use core::num::ParseIntError;
+    use crate::ParseError;
 
     #[doc(hidden)]
     pub trait Decimal: Sized {
-        fn from_str_decimal(src: &str) -> Result<Self, ParseIntError>;
+        fn from_str_decimal(src: &str) -> Result<Self, ParseError>;
         fn write_decimal(&self, f: &mut impl Write);
     }
 
     impl Decimal for I256 {
-        fn from_str_decimal(src: &str) -> Result<Self, ParseIntError> {
-            Self::from_str_radix(src, 10)
+        fn from_str_decimal(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_radix_detailed(src, 10)
         }
         fn write_decimal(&self, f: &mut impl Write) {
             write!(f, "{self}").expect("unexpected formatting error")
@@ -83,8 +183,8 @@ pub mod decimal {
     }
 
     impl Decimal for U256 {
-        fn from_str_decimal(src: &str) -> Result<Self, ParseIntError> {
-            Self::from_str_radix(src, 10)
+        fn from_str_decimal(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_radix_detailed(src, 10)
         }
         fn write_decimal(&self, f: &mut impl Write) {
             write!(f, "{self}").expect("unexpected formatting error")
@@ -97,7 +197,7 @@ pub mod decimal {
         T: Decimal,
         S: Serializer,
     {
-        let mut f = FormatBuffer::decimal();
+        let mut f = decimal_buffer();
         value.write_decimal(&mut f);
         serializer.serialize_str(f.as_str())
     }
@@ -119,22 +219,22 @@ pub mod decimal {
 /// if it is a hexadecimal string or decimal string.
 pub mod prefixed {
     use super::*;
-    use core::num::ParseIntError;
+    use crate::ParseError;
 
     #[doc(hidden)]
-    pub trait Prefixed: Serialize + Sized {
-        fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError>;
+    pub trait Prefixed: quantity::Quantity {
+        fn from_str_prefixed(src: &str) -> Result<Self, ParseError>;
     }
 
     impl Prefixed for I256 {
-        fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
-            Self::from_str_prefixed(src)
+        fn from_str_prefixed(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_prefixed_detailed(src)
         }
     }
 
     impl Prefixed for U256 {
-        fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
-            Self::from_str_prefixed(src)
+        fn from_str_prefixed(src: &str) -> Result<Self, ParseError> {
+            Self::from_str_prefixed_detailed(src)
         }
     }
 
@@ -144,7 +244,7 @@ pub mod prefixed {
         T: Prefixed,
         S: Serializer,
     {
-        value.serialize(serializer)
+        quantity::serialize(value, serializer)
     }
 
     #[doc(hidden)]
@@ -163,7 +263,7 @@ pub mod prefixed {
 /// This allows serialization to also accept standard numerical types as values
 /// in addition to prefixed strings.
 pub mod permissive {
-    use super::{prefixed::Prefixed, FormatVisitor};
+    use super::{prefixed::Prefixed, quantity, FormatVisitor};
     use crate::{AsI256 as _, I256, U256};
     use core::fmt::{self, Formatter};
     use core::marker::PhantomData;
@@ -195,7 +295,7 @@ pub mod permissive {
         T: Permissive,
         S: Serializer,
     {
-        value.serialize(serializer)
+        quantity::serialize(value, serializer)
     }
 
     struct PermissiveVisitor<T>(PhantomData<T>);
@@ -297,6 +397,618 @@ pub mod permissive {
     }
 }
 
+/// Module for use with `#[serde(with = "ethnum::serde::number")]` to specify
+/// JSON number serialization for 256-bit integer types.
+///
+/// Integers that fit in a primitive `u64`, `i64`, `u128` or `i128` are
+/// serialized as native integers. Larger integers are serialized using the
+/// raw number token used by `serde_json` when its `arbitrary_precision`
+/// feature is enabled. Deserialization accepts native integers as well as raw
+/// number tokens, allowing integer literals larger than 128 bits to be read
+/// from JSON.
+///
+/// # Requires `serde_json/arbitrary_precision`
+///
+/// This module relies on `serde_json` being compiled with its
+/// `arbitrary_precision` feature, and **does not work correctly without it**:
+///
+/// * Integers that do not fit in 128 bits are silently serialized as a JSON
+///   object of the form `{"$serde_json::private::Number":"..."}` instead of
+///   a number.
+/// * JSON numbers that do not fit in 64 bits are parsed by `serde_json` as
+///   (lossy) floating point numbers, which are rejected by this module. This
+///   means that integers serialized as native 128-bit numbers can't be read
+///   back either.
+///
+/// Since Cargo unifies features, enabling it anywhere in the dependency graph
+/// is sufficient:
+///
+/// ```toml
+/// serde_json = { version = "1", features = ["arbitrary_precision"] }
+/// ```
+pub mod number {
+    use super::{decimal::Decimal, decimal_buffer, FormatVisitor};
+    use crate::{I256, U256};
+    use core::{
+        convert::TryFrom,
+        fmt::{self, Formatter},
+        marker::PhantomData,
+    };
+    use serde::{
+        de::{self, Deserialize, Deserializer, MapAccess, Visitor},
+        ser::{SerializeStruct as _, Serializer},
+    };
+
+    /// The `serde_json` arbitrary precision number token.
+    const TOKEN: &str = "$serde_json::private::Number";
+
+    #[doc(hidden)]
+    pub trait Number: Decimal {
+        fn serialize_number<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer;
+        fn from_i128(value: i128) -> Option<Self>;
+        fn from_u128(value: u128) -> Option<Self>;
+    }
+
+    impl Number for I256 {
+        fn serialize_number<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if let Ok(value) = i64::try_from(*self) {
+                serializer.serialize_i64(value)
+            } else if let Ok(value) = u64::try_from(*self) {
+                serializer.serialize_u64(value)
+            } else if let Ok(value) = i128::try_from(*self) {
+                serializer.serialize_i128(value)
+            } else if let Ok(value) = u128::try_from(*self) {
+                serializer.serialize_u128(value)
+            } else {
+                serialize_token(self, serializer)
+            }
+        }
+        fn from_i128(value: i128) -> Option<Self> {
+            Some(I256::new(value))
+        }
+        fn from_u128(value: u128) -> Option<Self> {
+            Some(U256::new(value).as_i256())
+        }
+    }
+
+    impl Number for U256 {
+        fn serialize_number<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if let Ok(value) = u64::try_from(*self) {
+                serializer.serialize_u64(value)
+            } else if let Ok(value) = u128::try_from(*self) {
+                serializer.serialize_u128(value)
+            } else {
+                serialize_token(self, serializer)
+            }
+        }
+        fn from_i128(value: i128) -> Option<Self> {
+            Some(U256::new(u128::try_from(value).ok()?))
+        }
+        fn from_u128(value: u128) -> Option<Self> {
+            Some(U256::new(value))
+        }
+    }
+
+    /// Serializes a value as an arbitrary precision number token.
+    fn serialize_token<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Decimal,
+        S: Serializer,
+    {
+        let mut f = decimal_buffer();
+        value.write_decimal(&mut f);
+        let mut s = serializer.serialize_struct(TOKEN, 1)?;
+        s.serialize_field(TOKEN, f.as_str())?;
+        s.end()
+    }
+
+    #[doc(hidden)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Number,
+        S: Serializer,
+    {
+        value.serialize_number(serializer)
+    }
+
+    /// Arbitrary precision number token map key.
+    struct NumberKey;
+
+    impl<'de> Deserialize<'de> for NumberKey {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct KeyVisitor;
+
+            impl<'de> Visitor<'de> for KeyVisitor {
+                type Value = NumberKey;
+
+                fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                    f.write_str("a valid number field")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    if v == TOKEN {
+                        Ok(NumberKey)
+                    } else {
+                        Err(de::Error::custom("expected JSON number"))
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(KeyVisitor)
+        }
+    }
+
+    /// Arbitrary precision number token map value.
+    struct NumberValue<T>(T);
+
+    impl<'de, T> Deserialize<'de> for NumberValue<T>
+    where
+        T: Decimal,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer
+                .deserialize_str(FormatVisitor(T::from_str_decimal))
+                .map(NumberValue)
+        }
+    }
+
+    struct NumberVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for NumberVisitor<T>
+    where
+        T: Number,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            f.write_str("an integer number")
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_i128(v.into())
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_u128(v.into())
+        }
+
+        fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            T::from_i128(v).ok_or_else(|| de::Error::custom("integer out of range"))
+        }
+
+        fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            T::from_u128(v).ok_or_else(|| de::Error::custom("integer out of range"))
+        }
+
+        fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Err(de::Error::custom(
+                "invalid conversion from floating point number to 256-bit \
+                 integer, enable `serde_json/arbitrary_precision` for large \
+                 integer numbers",
+            ))
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let NumberKey = map
+                .next_key()?
+                .ok_or_else(|| de::Error::custom("expected JSON number"))?;
+            let NumberValue(value) = map.next_value()?;
+            Ok(value)
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Number,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NumberVisitor(PhantomData))
+    }
+}
+
+/// Module for use with `#[serde(with = "ethnum::serde::bytes32")]` to specify
+/// fixed-size 32-byte hexadecimal string serialization for 256-bit integer
+/// types.
+///
+/// This serializes integers as `DATA` values as specified in the Ethereum RPC,
+/// such as storage slots or event topics. That is, integers are encoded as
+/// `"0x"` prefixed strings with exactly 64 hexadecimal digits. Signed integers
+/// are encoded as their two's complement representation.
+///
+/// Deserialization only accepts this canonical form: a lower-case `"0x"`
+/// prefix followed by exactly 64 hexadecimal digits (in either case).
+pub mod bytes32 {
+    use super::*;
+
+    #[doc(hidden)]
+    pub trait Bytes32: Sized {
+        fn from_u256(value: U256) -> Self;
+        fn write_bytes32(&self, f: &mut impl Write);
+    }
+
+    impl Bytes32 for I256 {
+        fn from_u256(value: U256) -> Self {
+            value.as_i256()
+        }
+        fn write_bytes32(&self, f: &mut impl Write) {
+            write!(f, "{self:#066x}").expect("unexpected formatting failure")
+        }
+    }
+
+    impl Bytes32 for U256 {
+        fn from_u256(value: U256) -> Self {
+            value
+        }
+        fn write_bytes32(&self, f: &mut impl Write) {
+            write!(f, "{self:#066x}").expect("unexpected formatting failure")
+        }
+    }
+
+    fn from_str_bytes32<T>(src: &str) -> Result<T, &'static str>
+    where
+        T: Bytes32,
+    {
+        let digits = src
+            .strip_prefix("0x")
+            .filter(|digits| digits.len() == 64)
+            .ok_or("expected '0x' prefixed string of 64 hexadecimal digits")?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err("invalid hexadecimal digit");
+        }
+        let value = U256::from_str_radix(digits, 16).map_err(|_| "invalid hexadecimal digit")?;
+        Ok(T::from_u256(value))
+    }
+
+    #[doc(hidden)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Bytes32,
+        S: Serializer,
+    {
+        let mut f = FormatBuffer::<66>::new();
+        value.write_bytes32(&mut f);
+        serializer.serialize_str(f.as_str())
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Bytes32,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FormatVisitor(from_str_bytes32::<T>))
+    }
+}
+
+/// Module for use with `#[serde(with = "ethnum::serde::bignum")]` to specify
+/// CBOR bignum serialization for 256-bit integer types.
+///
+/// Integers are encoded following RFC 8949 section 3.4.3. Values that fit in
+/// a CBOR major type 0 or 1 integer use that preferred serialization.
+/// Larger values use a tagged byte string: tag 2 holds a non-negative value,
+/// and tag 3 holds `-1 - n` for a negative value `n`. The byte string is the
+/// big endian magnitude without leading zero bytes.
+///
+/// Tags are written through the serde conventions used by `ciborium` (the
+/// `"@@TAG@@"` enum), so this module has no effect on the encoding of other
+/// formats. Deserialization accepts plain integers as well as tagged bignums
+/// with leading zeros, and rejects values that do not fit in the target type.
+pub mod bignum {
+    use super::*;
+    use core::marker::PhantomData;
+    use serde::{
+        de::{EnumAccess, VariantAccess},
+        ser::SerializeTupleVariant as _,
+    };
+
+    /// The `ciborium` enum name used for tagged values.
+    const TAG: &str = "@@TAG@@";
+    /// The `ciborium` enum variants for untagged and tagged values.
+    const VARIANTS: &[&str] = &["@@UNTAGGED@@", "@@TAGGED@@"];
+
+    /// The RFC 8949 tag for unsigned bignums.
+    const POSITIVE: u64 = 2;
+    /// The RFC 8949 tag for negative bignums.
+    const NEGATIVE: u64 = 3;
+
+    #[doc(hidden)]
+    pub trait Bignum: Sized {
+        fn to_bignum(&self) -> (bool, U256);
+        fn from_bignum(negative: bool, magnitude: U256) -> Option<Self>;
+    }
+
+    impl Bignum for I256 {
+        fn to_bignum(&self) -> (bool, U256) {
+            if self.is_negative() {
+                (true, (!*self).as_u256())
+            } else {
+                (false, self.as_u256())
+            }
+        }
+        fn from_bignum(negative: bool, magnitude: U256) -> Option<Self> {
+            let value = I256::try_from(magnitude).ok()?;
+            Some(if negative { !value } else { value })
+        }
+    }
+
+    impl Bignum for U256 {
+        fn to_bignum(&self) -> (bool, U256) {
+            (false, *self)
+        }
+        fn from_bignum(negative: bool, magnitude: U256) -> Option<Self> {
+            (!negative).then_some(magnitude)
+        }
+    }
+
+    /// Byte string wrapper, so that the bignum magnitude is serialized as
+    /// CBOR bytes and not as an array.
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Bignum,
+        S: Serializer,
+    {
+        let (negative, magnitude) = value.to_bignum();
+        if let Ok(magnitude) = u64::try_from(magnitude) {
+            return match (negative, i64::try_from(magnitude)) {
+                (false, _) => serializer.serialize_u64(magnitude),
+                (true, Ok(magnitude)) => serializer.serialize_i64(-1 - magnitude),
+                (true, Err(_)) => serializer.serialize_i128(-1 - i128::from(magnitude)),
+            };
+        }
+
+        let bytes = magnitude.to_be_bytes();
+        let start = (magnitude.leading_zeros() / 8) as usize;
+        let tag = if negative { NEGATIVE } else { POSITIVE };
+
+        let mut variant = serializer.serialize_tuple_variant(TAG, 1, VARIANTS[1], 2)?;
+        variant.serialize_field(&tag)?;
+        variant.serialize_field(&Bytes(&bytes[start..]))?;
+        variant.end()
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Bignum,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(TAG, VARIANTS, BignumVisitor(PhantomData))
+    }
+
+    /// Converts a bignum to the target type, failing if it does not fit.
+    fn convert<T, E>(negative: bool, magnitude: U256) -> Result<T, E>
+    where
+        T: Bignum,
+        E: de::Error,
+    {
+        T::from_bignum(negative, magnitude).ok_or_else(|| de::Error::custom("integer out of range"))
+    }
+
+    /// The variant of a `ciborium` tagged value.
+    enum Variant {
+        Untagged,
+        Tagged,
+    }
+
+    impl<'de> Deserialize<'de> for Variant {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_identifier(VariantVisitor)
+        }
+    }
+
+    struct VariantVisitor;
+
+    impl Visitor<'_> for VariantVisitor {
+        type Value = Variant;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            f.write_str("a CBOR tag variant")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "@@UNTAGGED@@" => Ok(Variant::Untagged),
+                "@@TAGGED@@" => Ok(Variant::Tagged),
+                _ => Err(de::Error::unknown_variant(v, VARIANTS)),
+            }
+        }
+    }
+
+    struct BignumVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for BignumVisitor<T>
+    where
+        T: Bignum,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            f.write_str("a CBOR integer or tagged bignum")
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: EnumAccess<'de>,
+        {
+            match data.variant()? {
+                (Variant::Untagged, access) => access.newtype_variant_seed(IntegerVisitor(self.0)),
+                (Variant::Tagged, access) => access.tuple_variant(2, self),
+            }
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let negative = match seq.next_element::<u64>()? {
+                Some(POSITIVE) => false,
+                Some(NEGATIVE) => true,
+                Some(tag) => {
+                    return Err(de::Error::custom(format_args!(
+                        "unexpected CBOR tag {tag}, expected a bignum"
+                    )))
+                }
+                None => return Err(de::Error::invalid_length(0, &self)),
+            };
+            let magnitude = seq
+                .next_element_seed(MagnitudeVisitor)?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            convert(negative, magnitude)
+        }
+    }
+
+    /// Visitor for plain CBOR integers.
+    struct IntegerVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::DeserializeSeed<'de> for IntegerVisitor<T>
+    where
+        T: Bignum,
+    {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    }
+
+    impl<T> Visitor<'_> for IntegerVisitor<T>
+    where
+        T: Bignum,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            f.write_str("a CBOR integer")
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_i128(v.into())
+        }
+
+        fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if v < 0 {
+                convert(true, U256::new(!v as u128))
+            } else {
+                convert(false, U256::new(v as u128))
+            }
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_u128(v.into())
+        }
+
+        fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            convert(false, U256::new(v))
+        }
+    }
+
+    /// Visitor for the big endian magnitude byte string of a bignum.
+    struct MagnitudeVisitor;
+
+    impl<'de> de::DeserializeSeed<'de> for MagnitudeVisitor {
+        type Value = U256;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_bytes(self)
+        }
+    }
+
+    impl Visitor<'_> for MagnitudeVisitor {
+        type Value = U256;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            f.write_str("a big endian byte string of at most 32 significant bytes")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let start = v.iter().position(|&b| b != 0).unwrap_or(v.len());
+            let v = &v[start..];
+            if v.len() > 32 {
+                return Err(de::Error::custom("integer out of range"));
+            }
+
+            let mut bytes = [0; 32];
+            bytes[32 - v.len()..].copy_from_slice(v);
+            Ok(U256::from_be_bytes(bytes))
+        }
+    }
+}
+
 /// Internal visitor struct implementation to facilitate implementing different
 /// serialization formats.
 struct FormatVisitor<F>(F);
@@ -329,73 +1041,58 @@ where
     }
 }
 
-/// A stack-allocated buffer that can be used for writing formatted strings.
-///
-/// This allows us to leverage existing `fmt` implementations on integer types
-/// without requiring heap allocations (i.e. writing to a `String` buffer).
-struct FormatBuffer<const N: usize> {
-    offset: usize,
-    buffer: [MaybeUninit<u8>; N],
-}
+/// Internal visitor struct implementation for deserializing 256-bit integers
+/// from fixed 32-byte big endian byte strings, also accepting sequences of
+/// bytes for formats that do not natively support byte strings.
+struct BytesVisitor<F>(F);
 
-impl<const N: usize> FormatBuffer<N> {
-    /// Creates a new formatting buffer.
-    fn new() -> Self {
-        Self {
-            offset: 0,
-            buffer: [MaybeUninit::uninit(); N],
-        }
-    }
+impl<'de, T, F> Visitor<'de> for BytesVisitor<F>
+where
+    F: FnOnce([u8; 32]) -> T,
+{
+    type Value = T;
 
-    /// Returns a `str` to the currently written data.
-    fn as_str(&self) -> &str {
-        // SAFETY: We only ever write valid UTF-8 strings to the buffer, so the
-        // resulting string will always be valid.
-        unsafe {
-            let buffer = slice::from_raw_parts(self.buffer[0].as_ptr(), self.offset);
-            str::from_utf8_unchecked(buffer)
-        }
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("32 big endian bytes")
     }
-}
 
-impl FormatBuffer<78> {
-    /// Allocates a formatting buffer large enough to hold any possible decimal
-    /// encoded 256-bit value.
-    fn decimal() -> Self {
-        Self::new()
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let bytes = v
+            .try_into()
+            .map_err(|_| de::Error::invalid_length(v.len(), &self))?;
+        Ok(self.0(bytes))
     }
-}
 
-impl FormatBuffer<67> {
-    /// Allocates a formatting buffer large enough to hold any possible
-    /// hexadecimal encoded 256-bit value.
-    fn hex() -> Self {
-        Self::new()
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(33, &self));
+        }
+        Ok(self.0(bytes))
     }
 }
 
-impl<const N: usize> Write for FormatBuffer<N> {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        let end = self.offset.checked_add(s.len()).ok_or(fmt::Error)?;
-
-        // Make sure there is enough space in the buffer.
-        if end > N {
-            return Err(fmt::Error);
-        }
-
-        // SAFETY: We checked that there is enough space in the buffer to fit
-        // the string `s` starting from `offset`, and the pointers cannot be
-        // overlapping because of Rust ownership semantics (i.e. `s` cannot
-        // overlap with `buffer` because we have a mutable reference to `self`
-        // and by extension `buffer`).
-        unsafe {
-            let buffer = self.buffer[0].as_mut_ptr().add(self.offset);
-            ptr::copy_nonoverlapping(s.as_ptr(), buffer, s.len());
-        }
-        self.offset = end;
+/// Allocates a formatting buffer large enough to hold any possible decimal
+/// encoded 256-bit value.
+fn decimal_buffer() -> FormatBuffer<78> {
+    FormatBuffer::new()
+}
 
-        Ok(())
-    }
+/// Allocates a formatting buffer large enough to hold any possible hexadecimal
+/// encoded 256-bit value.
+fn hex_buffer() -> FormatBuffer<67> {
+    FormatBuffer::new()
 }
 
 #[cfg(test)]
@@ -406,6 +1103,7 @@ mod tests {
         fmt::{Display, LowerHex},
         format,
         string::String,
+        vec::Vec,
     };
     use serde::{
         de::{value, IntoDeserializer},
@@ -451,6 +1149,23 @@ mod tests {
             "115792089237316195423570985008687907853269984665640564039457584007913129639935",
         );
 
+        assert_eq!(ser!(quantity::serialize, I256::new(-42)), "-0x2a");
+        assert_eq!(ser!(quantity::serialize, U256::new(42)), "0x2a");
+        assert_eq!(ser!(quantity_strict::serialize, U256::new(0)), "0x0");
+        assert_eq!(ser!(quantity_strict::serialize, U256::new(1024)), "0x400");
+        assert_eq!(
+            ser!(bytes32::serialize, U256::new(42)),
+            "0x000000000000000000000000000000000000000000000000000000000000002a",
+        );
+        assert_eq!(
+            ser!(bytes32::serialize, U256::MAX),
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        );
+        assert_eq!(
+            ser!(bytes32::serialize, I256::new(-2)),
+            "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+        );
+
         assert_eq!(ser!(prefixed::serialize, I256::new(42)), "0x2a");
         assert_eq!(ser!(permissive::serialize, I256::new(42)), "0x2a");
     }
@@ -512,6 +1227,42 @@ mod tests {
             U256::MAX
         );
 
+        assert_eq!(
+            de!(
+                bytes32::deserialize::<U256, _>,
+                "0x000000000000000000000000000000000000000000000000000000000000002A"
+            ),
+            U256::new(42)
+        );
+        assert_eq!(
+            de!(
+                bytes32::deserialize::<I256, _>,
+                "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe"
+            ),
+            I256::new(-2)
+        );
+        for src in [
+            "0x2a",
+            "0x",
+            "",
+            "2a00000000000000000000000000000000000000000000000000000000000000",
+            "0X000000000000000000000000000000000000000000000000000000000000002a",
+            "0x+00000000000000000000000000000000000000000000000000000000000002a",
+            "0x-00000000000000000000000000000000000000000000000000000000000002a",
+            "0x00000000000000000000000000000000000000000000000000000000000002ag",
+            "0x0000000000000000000000000000000000000000000000000000000000000002a",
+            " 0x000000000000000000000000000000000000000000000000000000000000002a",
+        ] {
+            assert!(de!(err; bytes32::deserialize::<U256, _>, src));
+            assert!(de!(err; bytes32::deserialize::<I256, _>, src));
+        }
+
+        assert_eq!(de!(quantity_strict::deserialize, "0x0"), U256::new(0));
+        assert_eq!(de!(quantity_strict::deserialize, "0x400"), U256::new(1024));
+        for src in ["", "0x", "0x0400", "0X400", "+0x400", "400", "0x 400"] {
+            assert!(de!(err; quantity_strict::deserialize, src));
+        }
+
         assert_eq!(de!(prefixed::deserialize::<I256, _>, "-1"), I256::new(-1));
         assert_eq!(de!(prefixed::deserialize::<I256, _>, "-0x1"), I256::new(-1));
         assert_eq!(de!(prefixed::deserialize::<I256, _>, "42"), I256::new(42));
@@ -627,6 +1378,231 @@ mod tests {
         );
     }
 
+    #[test]
+    fn binary_integers() {
+        // Binary formats encode the 32 bytes with at most a small length
+        // prefix. Note that `bincode` always uses a 64-bit length.
+        fn encoded_len<T: Serialize>(value: &T) -> [usize; 3] {
+            let mut cbor = Vec::new();
+            ciborium::into_writer(value, &mut cbor).unwrap();
+            [
+                bincode::serialized_size(value).unwrap() as _,
+                cbor.len(),
+                rmp_serde::to_vec(value).unwrap().len(),
+            ]
+        }
+        for value in [U256::ZERO, U256::new(4919), U256::MAX] {
+            assert_eq!(encoded_len(&value), [40, 34, 34]);
+            let encoded = bincode::serialize(&value).unwrap();
+            assert_eq!(encoded[..8], 32_u64.to_le_bytes());
+            assert_eq!(encoded[8..], value.to_be_bytes());
+            assert_eq!(bincode::deserialize::<U256>(&encoded).unwrap(), value);
+
+            let mut cbor = Vec::new();
+            ciborium::into_writer(&value, &mut cbor).unwrap();
+            assert_eq!(ciborium::from_reader::<U256, _>(&cbor[..]).unwrap(), value);
+            let msgpack = rmp_serde::to_vec(&value).unwrap();
+            assert_eq!(rmp_serde::from_slice::<U256>(&msgpack).unwrap(), value);
+        }
+        for value in [I256::MIN, I256::new(-1), I256::new(42), I256::MAX] {
+            assert_eq!(encoded_len(&value), [40, 34, 34]);
+            let encoded = bincode::serialize(&value).unwrap();
+            assert_eq!(encoded[8..], value.to_be_bytes());
+            assert_eq!(bincode::deserialize::<I256>(&encoded).unwrap(), value);
+
+            let mut cbor = Vec::new();
+            ciborium::into_writer(&value, &mut cbor).unwrap();
+            assert_eq!(ciborium::from_reader::<I256, _>(&cbor[..]).unwrap(), value);
+            let msgpack = rmp_serde::to_vec(&value).unwrap();
+            assert_eq!(rmp_serde::from_slice::<I256>(&msgpack).unwrap(), value);
+        }
+
+        for len in [31, 33] {
+            let encoded = bincode::serialize(&[0_u8; 33][..len]).unwrap();
+            assert!(bincode::deserialize::<U256>(&encoded).is_err());
+        }
+
+        let seq = |len: usize| {
+            BytesVisitor(U256::from_be_bytes).visit_seq(
+                value::SeqDeserializer::<_, value::Error>::new(1..=len as u8),
+            )
+        };
+        assert_eq!(
+            seq(32).unwrap(),
+            U256::from_be_bytes(core::array::from_fn(|i| i as u8 + 1)),
+        );
+        assert!(seq(31).is_err());
+        assert!(seq(33).is_err());
+
+        struct Quantity(U256);
+        impl Serialize for Quantity {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                quantity::serialize(&self.0, serializer)
+            }
+        }
+        impl<'de> Deserialize<'de> for Quantity {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                quantity::deserialize(deserializer).map(Quantity)
+            }
+        }
+
+        let encoded = bincode::serialize(&Quantity(U256::new(4919))).unwrap();
+        assert_eq!(encoded[8..], *b"0x1337");
+        assert_eq!(
+            bincode::deserialize::<Quantity>(&encoded).unwrap().0,
+            U256::new(4919),
+        );
+    }
+
+    #[test]
+    fn json_numbers() {
+        struct Number<T>(T);
+        impl<T: number::Number> Serialize for Number<T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                number::serialize(&self.0, serializer)
+            }
+        }
+        impl<'de, T: number::Number> Deserialize<'de> for Number<T> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                number::deserialize(deserializer).map(Number)
+            }
+        }
+
+        fn round_trip<T>(value: T, json: &str)
+        where
+            T: number::Number + Copy + PartialEq + core::fmt::Debug,
+        {
+            assert_eq!(serde_json::to_string(&Number(value)).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Number<T>>(json).unwrap().0, value);
+        }
+
+        // Round trips of integers that do not fit in 64 bits require the
+        // `arbitrary_precision` feature, and are tested in the `ethnum-json`
+        // crate. Here, only check that smaller integers work without it.
+        round_trip(U256::ZERO, "0");
+        round_trip(U256::new(42), "42");
+        round_trip(U256::new(u64::MAX.into()), "18446744073709551615");
+        round_trip(I256::new(-42), "-42");
+        round_trip(I256::new(i64::MIN.into()), "-9223372036854775808");
+
+        // Cargo unifies `serde_json` features across the workspace, so the
+        // failure modes below can only be observed when nothing else enabled
+        // `arbitrary_precision`.
+        let value = serde_json::from_str::<serde_json::Value>("18446744073709551616").unwrap();
+        let arbitrary_precision = format!("{value}") == "18446744073709551616";
+        if !arbitrary_precision {
+            assert_eq!(
+                serde_json::to_string(&Number(U256::MAX)).unwrap(),
+                "{\"$serde_json::private::Number\":\
+                  \"115792089237316195423570985008687907853269984665640564039457584007913129639935\"}",
+            );
+            assert!(serde_json::from_str::<Number<U256>>(
+                "340282366920938463463374607431768211455"
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn cbor_bignums() {
+        struct Bignum<T>(T);
+        impl<T: bignum::Bignum> Serialize for Bignum<T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                bignum::serialize(&self.0, serializer)
+            }
+        }
+        impl<'de, T: bignum::Bignum> Deserialize<'de> for Bignum<T> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                bignum::deserialize(deserializer).map(Bignum)
+            }
+        }
+
+        fn encode<T: bignum::Bignum>(value: T) -> Vec<u8> {
+            let mut buffer = Vec::new();
+            ciborium::into_writer(&Bignum(value), &mut buffer).unwrap();
+            buffer
+        }
+        fn decode<T: bignum::Bignum>(cbor: &[u8]) -> Option<T> {
+            ciborium::from_reader::<Bignum<T>, _>(cbor)
+                .ok()
+                .map(|b| b.0)
+        }
+        fn round_trip<T>(value: T, cbor: &[u8])
+        where
+            T: bignum::Bignum + Copy + PartialEq + core::fmt::Debug,
+        {
+            assert_eq!(encode(value), cbor);
+            assert_eq!(decode::<T>(cbor), Some(value));
+        }
+
+        let bytes = |head: &[u8], tail: &[u8]| [head, tail].concat();
+
+        round_trip(U256::ZERO, &[0x00]);
+        round_trip(U256::new(42), &[0x18, 0x2a]);
+        round_trip(U256::new(u64::MAX.into()), &bytes(&[0x1b], &[0xff; 8]));
+        round_trip(U256::new(1 << 64), &bytes(&[0xc2, 0x49, 0x01], &[0; 8]));
+        round_trip(U256::MAX, &bytes(&[0xc2, 0x58, 0x20], &[0xff; 32]));
+
+        round_trip(I256::MINUS_ONE, &[0x20]);
+        round_trip(
+            I256::new(i64::MIN.into()),
+            &bytes(&[0x3b, 0x7f], &[0xff; 7]),
+        );
+        round_trip(I256::new(-(1 << 64)), &bytes(&[0x3b], &[0xff; 8]));
+        round_trip(
+            I256::new(-(1 << 64) - 1),
+            &bytes(&[0xc3, 0x49, 0x01], &[0; 8]),
+        );
+        round_trip(I256::MAX, &bytes(&[0xc2, 0x58, 0x20, 0x7f], &[0xff; 31]));
+        round_trip(I256::MIN, &bytes(&[0xc3, 0x58, 0x20, 0x7f], &[0xff; 31]));
+
+        // Non-canonical leading zeros are accepted.
+        assert_eq!(
+            decode::<U256>(&bytes(&[0xc2, 0x58, 0x21, 0x00], &[0xff; 32])),
+            Some(U256::MAX),
+        );
+        assert_eq!(
+            decode::<U256>(&[0xc2, 0x42, 0x00, 0x2a]),
+            Some(U256::new(42))
+        );
+
+        // Out of range values and unexpected tags are rejected.
+        assert_eq!(decode::<U256>(&[0x20]), None);
+        assert_eq!(decode::<U256>(&[0xc3, 0x41, 0x01]), None);
+        assert_eq!(
+            decode::<U256>(&bytes(&[0xc2, 0x58, 0x21, 0x01], &[0; 32])),
+            None
+        );
+        assert_eq!(
+            decode::<I256>(&bytes(&[0xc2, 0x58, 0x20, 0x80], &[0; 31])),
+            None
+        );
+        assert_eq!(
+            decode::<I256>(&bytes(&[0xc3, 0x58, 0x20, 0x80], &[0; 31])),
+            None
+        );
+        assert_eq!(decode::<U256>(&[0xc4, 0x41, 0x01]), None);
+        assert_eq!(decode::<U256>(&[0x41, 0x01]), None);
+    }
+
     #[test]
     fn formatting_buffer() {
         for value in [
@@ -635,7 +1611,7 @@ mod tests {
             Box::new(U256::MIN),
             Box::new(U256::MAX),
         ] {
-            let mut f = FormatBuffer::decimal();
+            let mut f = decimal_buffer();
             write!(f, "{value}").unwrap();
             assert_eq!(f.as_str(), format!("{value}"));
         }
@@ -646,7 +1622,7 @@ mod tests {
             Box::new(U256::MIN),
             Box::new(U256::MAX),
         ] {
-            let mut f = FormatBuffer::hex();
+            let mut f = hex_buffer();
             let value = &*value;
             write!(f, "{value:-#x}").unwrap();
             assert_eq!(f.as_str(), format!("{value:-#x}"));
@@ -711,9 +1687,9 @@ mod tests {
         fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
             unimplemented!()
         }
-        fn serialize_some<T: ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error>
+        fn serialize_some<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
         where
-            T: Serialize,
+            T: Serialize + ?Sized,
         {
             unimplemented!()
         }
@@ -731,17 +1707,17 @@ mod tests {
         ) -> Result<Self::Ok, Self::Error> {
             unimplemented!()
         }
-        fn serialize_newtype_struct<T: ?Sized>(
+        fn serialize_newtype_struct<T>(
             self,
             _: &'static str,
             _: &T,
         ) -> Result<Self::Ok, Self::Error>
         where
-            T: Serialize,
+            T: Serialize + ?Sized,
         {
             unimplemented!()
         }
-        fn serialize_newtype_variant<T: ?Sized>(
+        fn serialize_newtype_variant<T>(
             self,
             _: &'static str,
             _: u32,
@@ -749,7 +1725,7 @@ mod tests {
             _: &T,
         ) -> Result<Self::Ok, Self::Error>
         where
-            T: Serialize,
+            T: Serialize + ?Sized,
         {
             unimplemented!()
         }
@@ -0,0 +1,105 @@
+//! SSZ (SimpleSerialize) implementation for 256-bit unsigned integers.
+//!
+//! This implementation follows the consensus-layer specification for the
+//! `uint256` basic type. That is, values are encoded as fixed-size 32-byte
+//! little endian integers:
+//! <https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md>
+
+use crate::uint::U256;
+use alloc::vec::Vec;
+use ssz::{Decode, DecodeError, Encode};
+
+/// The number of bytes in a fixed-size SSZ encoded `uint256`.
+const BYTES_LEN: usize = 32;
+
+impl Encode for U256 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        BYTES_LEN
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        BYTES_LEN
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl Decode for U256 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        BYTES_LEN
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: BYTES_LEN,
+            })?;
+        Ok(Self::from_le_bytes(bytes))
+    }
+}
+
+/// Computes the SSZ `hash_tree_root` of a `uint256` basic value.
+///
+/// Since a `uint256` fills exactly one 32-byte chunk, its hash tree root is
+/// the chunk itself and no hashing is required.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{ssz::hash_tree_root, U256};
+/// let mut root = [0; 32];
+/// root[0] = 42;
+/// assert_eq!(hash_tree_root(&U256::new(42)), root);
+/// ```
+pub fn hash_tree_root(value: &U256) -> [u8; BYTES_LEN] {
+    value.to_le_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            U256::ZERO,
+            U256::ONE,
+            U256::from_words(0x0123456789abcdef, 0xfedcba9876543210),
+            U256::MAX,
+        ] {
+            let bytes = value.as_ssz_bytes();
+            assert_eq!(bytes, value.to_le_bytes());
+            assert_eq!(U256::from_ssz_bytes(&bytes), Ok(value));
+        }
+    }
+
+    #[test]
+    fn invalid_length() {
+        for len in [0, 31, 33] {
+            assert_eq!(
+                U256::from_ssz_bytes(&alloc::vec![0; len]),
+                Err(DecodeError::InvalidByteLength { len, expected: 32 }),
+            );
+        }
+    }
+
+    #[test]
+    fn encoded_in_containers() {
+        let values = alloc::vec![U256::ONE, U256::MAX];
+        let bytes = values.as_ssz_bytes();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(Vec::<U256>::from_ssz_bytes(&bytes), Ok(values));
+    }
+}
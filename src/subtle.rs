@@ -0,0 +1,293 @@
+//! Constant-time operations on 256-bit integer types.
+//!
+//! The general purpose integer operations in this crate are optimized for
+//! speed, and some of them branch on operand values. In particular, `Ord`
+//! comparisons return as soon as the most significant words differ, and
+//! division exits early depending on the operands' bit lengths. This makes
+//! them unsuitable for arithmetic on secret values, such as private keys or
+//! nonces, where execution time must not depend on the data.
+//!
+//! This module implements the `subtle` crate's constant-time traits for the
+//! 256-bit integer types, as well as a small set of constant-time modular
+//! arithmetic functions:
+//!
+//! ```
+//! # use ethnum::{subtle::{add_mod, mul_mod}, U256};
+//! use subtle::{ConditionallySelectable, ConstantTimeEq, ConstantTimeLess};
+//!
+//! let m = U256::MAX - 188; // 2^256 - 189 is prime
+//! let (a, b) = (U256::new(42), m - 1);
+//!
+//! assert!(bool::from(a.ct_lt(&b)));
+//! assert!(bool::from(add_mod(a, b, m).ct_eq(&U256::new(41))));
+//! assert_eq!(mul_mod(b, b, m), U256::ONE);
+//! assert_eq!(U256::conditional_select(&a, &b, 1.into()), b);
+//! ```
+//!
+//! Only the operations in this module are constant-time. Note that these
+//! guarantees are best-effort, as the compiler may still choose to introduce
+//! branches when optimizing code.
+
+use crate::{int::I256, uint::U256};
+use ::subtle::{
+    Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, ConstantTimeLess,
+};
+
+/// Computes `a - b`, returning the wrapped difference and whether or not the
+/// subtraction borrowed, without branching.
+#[inline]
+fn sub_borrow(a: U256, b: U256) -> (U256, Choice) {
+    let ((ah, al), (bh, bl)) = (a.into_words(), b.into_words());
+    let (lo, borrow_lo) = al.overflowing_sub(bl);
+    let (hi, borrow_hi) = ah.overflowing_sub(bh);
+    let (hi, borrow_c) = hi.overflowing_sub(borrow_lo as _);
+    (
+        U256::from_words(hi, lo),
+        Choice::from((borrow_hi | borrow_c) as u8),
+    )
+}
+
+/// Computes `a + b`, returning the wrapped sum and whether or not the addition
+/// carried, without branching.
+#[inline]
+fn add_carry(a: U256, b: U256) -> (U256, Choice) {
+    let ((ah, al), (bh, bl)) = (a.into_words(), b.into_words());
+    let (lo, carry_lo) = al.overflowing_add(bl);
+    let (hi, carry_hi) = ah.overflowing_add(bh);
+    let (hi, carry_c) = hi.overflowing_add(carry_lo as _);
+    (
+        U256::from_words(hi, lo),
+        Choice::from((carry_hi | carry_c) as u8),
+    )
+}
+
+/// Maps a signed integer to an unsigned integer with the same ordering.
+#[inline]
+fn signed_key(a: I256) -> U256 {
+    a.as_u256() ^ I256::MIN.as_u256()
+}
+
+impl ConstantTimeEq for U256 {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let ((ah, al), (bh, bl)) = (self.into_words(), other.into_words());
+        ah.ct_eq(&bh) & al.ct_eq(&bl)
+    }
+}
+
+impl ConstantTimeEq for I256 {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_u256().ct_eq(&other.as_u256())
+    }
+}
+
+impl ConstantTimeGreater for U256 {
+    #[inline]
+    fn ct_gt(&self, other: &Self) -> Choice {
+        sub_borrow(*other, *self).1
+    }
+}
+
+impl ConstantTimeGreater for I256 {
+    #[inline]
+    fn ct_gt(&self, other: &Self) -> Choice {
+        signed_key(*self).ct_gt(&signed_key(*other))
+    }
+}
+
+impl ConstantTimeLess for U256 {}
+impl ConstantTimeLess for I256 {}
+
+impl ConditionallySelectable for U256 {
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let ((ah, al), (bh, bl)) = (a.into_words(), b.into_words());
+        Self::from_words(
+            u128::conditional_select(&ah, &bh, choice),
+            u128::conditional_select(&al, &bl, choice),
+        )
+    }
+}
+
+impl ConditionallySelectable for I256 {
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        U256::conditional_select(&a.as_u256(), &b.as_u256(), choice).as_i256()
+    }
+}
+
+/// Computes `(a + b) % modulus` in constant time.
+///
+/// Both operands must already be reduced, i.e. less than `modulus`; the result
+/// is unspecified otherwise. Execution time only depends on the input sizes,
+/// and not on their values.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{subtle::add_mod, U256};
+/// let m = U256::new(7);
+/// assert_eq!(add_mod(U256::new(5), U256::new(4), m), 2);
+/// assert_eq!(add_mod(U256::MAX - 1, U256::MAX - 1, U256::MAX), U256::MAX - 2);
+/// ```
+#[inline]
+pub fn add_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    let (sum, carry) = add_carry(a, b);
+    let (reduced, borrow) = sub_borrow(sum, modulus);
+    // The sum needs reducing if it overflowed, or if it is at least `modulus`.
+    U256::conditional_select(&sum, &reduced, carry | !borrow)
+}
+
+/// Computes `(a - b) % modulus` in constant time.
+///
+/// Both operands must already be reduced, i.e. less than `modulus`; the result
+/// is unspecified otherwise. Execution time only depends on the input sizes,
+/// and not on their values.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{subtle::sub_mod, U256};
+/// let m = U256::new(7);
+/// assert_eq!(sub_mod(U256::new(5), U256::new(4), m), 1);
+/// assert_eq!(sub_mod(U256::new(4), U256::new(5), m), 6);
+/// ```
+#[inline]
+pub fn sub_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    let (difference, borrow) = sub_borrow(a, b);
+    let (wrapped, _) = add_carry(difference, modulus);
+    U256::conditional_select(&difference, &wrapped, borrow)
+}
+
+/// Computes `(a * b) % modulus` in constant time.
+///
+/// Both operands must already be reduced, i.e. less than `modulus`; the result
+/// is unspecified otherwise. The product is computed with a fixed sequence of
+/// 256 modular doublings and additions, so execution time only depends on the
+/// input sizes, and not on their values.
+///
+/// # Examples
+///
+/// ```
+/// # use ethnum::{subtle::mul_mod, U256};
+/// let m = U256::new(7);
+/// assert_eq!(mul_mod(U256::new(5), U256::new(4), m), 6);
+/// assert_eq!(mul_mod(U256::MAX - 1, U256::MAX - 1, U256::MAX), U256::ONE);
+/// ```
+pub fn mul_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    let (hi, lo) = b.into_words();
+    let mut result = U256::ZERO;
+    for word in [hi, lo] {
+        for i in (0..128).rev() {
+            result = add_mod(result, result, modulus);
+            let bit = Choice::from(((word >> i) & 1) as u8);
+            let sum = add_mod(result, a, modulus);
+            result.conditional_assign(&sum, bit);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparisons() {
+        let unsigned = [
+            U256::ZERO,
+            U256::ONE,
+            U256::new(u128::MAX),
+            U256::from_words(1, 0),
+            U256::from_words(1, u128::MAX),
+            U256::MAX - 1,
+            U256::MAX,
+        ];
+        for a in unsigned {
+            for b in unsigned {
+                assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+                assert_eq!(bool::from(a.ct_gt(&b)), a > b);
+                assert_eq!(bool::from(a.ct_lt(&b)), a < b);
+            }
+        }
+
+        let signed = [
+            I256::MIN,
+            I256::MIN + 1,
+            I256::new(i128::MIN),
+            I256::MINUS_ONE,
+            I256::ZERO,
+            I256::ONE,
+            I256::new(i128::MAX),
+            I256::MAX,
+        ];
+        for a in signed {
+            for b in signed {
+                assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+                assert_eq!(bool::from(a.ct_gt(&b)), a > b);
+                assert_eq!(bool::from(a.ct_lt(&b)), a < b);
+            }
+        }
+    }
+
+    #[test]
+    fn selection() {
+        let (mut a, mut b) = (I256::MIN, I256::MAX);
+        assert_eq!(I256::conditional_select(&a, &b, 0.into()), a);
+        assert_eq!(I256::conditional_select(&a, &b, 1.into()), b);
+
+        I256::conditional_swap(&mut a, &mut b, 0.into());
+        assert_eq!((a, b), (I256::MIN, I256::MAX));
+        I256::conditional_swap(&mut a, &mut b, 1.into());
+        assert_eq!((a, b), (I256::MAX, I256::MIN));
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let moduli = [
+            U256::new(3),
+            U256::new(7),
+            U256::from_words(1, 0x1234),
+            U256::MAX - 188,
+            U256::MAX,
+        ];
+        for m in moduli {
+            let values = [
+                U256::ZERO,
+                U256::ONE,
+                m >> 1,
+                (m >> 1) + U256::ONE,
+                U256::new(u128::MAX) % m,
+                m - 1,
+            ];
+            for a in values {
+                for b in values {
+                    let sum = a.overflowing_add(b);
+                    let expected = if sum.1 || sum.0 >= m {
+                        sum.0.wrapping_sub(m)
+                    } else {
+                        sum.0
+                    };
+                    assert_eq!(add_mod(a, b, m), expected);
+                    assert_eq!(sub_mod(add_mod(a, b, m), b, m), a);
+
+                    let product = mul_mod(a, b, m);
+                    assert!(product < m);
+                    assert_eq!(product, mul_mod(b, a, m));
+                }
+            }
+        }
+
+        let m = U256::MAX - 188;
+        assert_eq!(mul_mod(m - 1, m - 1, m), U256::ONE);
+        assert_eq!(
+            mul_mod(U256::new(u128::MAX), U256::new(u128::MAX), m),
+            U256::new(u128::MAX) * U256::new(u128::MAX)
+        );
+        assert_eq!(
+            mul_mod(U256::from_words(1, 0), U256::from_words(1, 0), m),
+            189
+        );
+    }
+}
@@ -8,11 +8,23 @@ mod iter;
 mod ops;
 
 pub use self::convert::AsU256;
-use crate::I256;
+use crate::{
+    error::{Integer, ParseError, QuantityError, TryFromIntError},
+    Divisor256, FormatBuffer, I256,
+};
 use core::num::ParseIntError;
 
 /// A 256-bit unsigned integer type.
 #[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::Immutable,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout
+    )
+)]
 #[repr(transparent)]
 pub struct U256(pub [u128; 2]);
 
@@ -57,6 +69,59 @@ impl U256 {
         }
     }
 
+    /// Creates a new 256-bit integer value from little endian 64-bit limbs,
+    /// the representation used by other 256-bit integer crates.
+    ///
+    /// This compiles down to a plain copy on little endian targets, where both
+    /// representations share the same memory layout.
+    #[inline]
+    pub(crate) const fn from_limbs(limbs: [u64; 4]) -> Self {
+        let [a, b, c, d] = limbs;
+        U256::from_words(
+            ((d as u128) << 64) | (c as u128),
+            ((b as u128) << 64) | (a as u128),
+        )
+    }
+
+    /// Splits a 256-bit integer into little endian 64-bit limbs.
+    #[inline]
+    pub(crate) const fn into_limbs(self) -> [u64; 4] {
+        let (hi, lo) = self.into_words();
+        [lo as _, (lo >> 64) as _, hi as _, (hi >> 64) as _]
+    }
+
+    /// Computes `self * mul + add` one 64-bit limb at a time, returning the
+    /// low 256 bits of the result along with the carried out high limb.
+    #[inline]
+    pub(crate) fn widening_mul_add_u64(self, mul: u64, add: u64) -> (Self, u64) {
+        let mut limbs = self.into_limbs();
+        let mut carry = add;
+        for limb in &mut limbs {
+            // This never overflows, since `(2^64 - 1)^2 + 2 * (2^64 - 1)` is
+            // exactly `u128::MAX`.
+            let x = (*limb as u128) * (mul as u128) + (carry as u128);
+            *limb = x as u64;
+            carry = (x >> 64) as u64;
+        }
+        (Self::from_limbs(limbs), carry)
+    }
+
+    /// Checked `self * mul + add`, returning `None` if overflow occurred.
+    #[inline]
+    pub(crate) fn checked_mul_add_u64(self, mul: u64, add: u64) -> Option<Self> {
+        match self.widening_mul_add_u64(mul, add) {
+            (x, 0) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Checked `self * mul - sub`, returning `None` if overflow occurred.
+    #[inline]
+    pub(crate) fn checked_mul_sub_u64(self, mul: u64, sub: u64) -> Option<Self> {
+        self.checked_mul_add_u64(mul, 0)?
+            .checked_sub(U256::new(sub as _))
+    }
+
     /// Get the low 128-bit word for this unsigned integer.
     #[inline]
     pub fn low(&self) -> &u128 {
@@ -126,9 +191,58 @@ impl U256 {
     /// assert_eq!(U256::from_str_hex("0x2A"), Ok(U256::new(42)));
     /// ```
     pub fn from_str_hex(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_hex_detailed(src).map_err(ParseIntError::from)
+    }
+
+    /// Converts a prefixed string slice in base 16 to an integer, returning a
+    /// detailed error on failure.
+    ///
+    /// This behaves exactly like [`U256::from_str_hex`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let err = U256::from_str_hex_detailed("0x2g").unwrap_err();
+    /// assert_eq!(err.offset(), 3);
+    /// ```
+    pub fn from_str_hex_detailed(src: &str) -> Result<Self, ParseError> {
         crate::fmt::from_str_radix(src, 16, Some("0x"))
     }
 
+    /// Converts an Ethereum JSON-RPC `QUANTITY` string to an integer.
+    ///
+    /// Unlike [`U256::from_str_hex`], this strictly follows the JSON-RPC
+    /// specification: the string must start with a lower-case `0x` prefix,
+    /// must contain at least one digit, and must not have extraneous leading
+    /// `0`s. Signs and whitespace represent an error. The returned error
+    /// indicates which of these rules was violated.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::{QuantityError, U256};
+    /// assert_eq!(U256::from_quantity_str("0x400"), Ok(U256::new(1024)));
+    /// assert_eq!(U256::from_quantity_str("0x0"), Ok(U256::new(0)));
+    /// assert_eq!(
+    ///     U256::from_quantity_str("0x0400"),
+    ///     Err(QuantityError::LeadingZero),
+    /// );
+    /// assert_eq!(
+    ///     U256::from_quantity_str("0x"),
+    ///     Err(QuantityError::EmptyNumber),
+    /// );
+    /// ```
+    pub fn from_quantity_str(src: &str) -> Result<Self, QuantityError> {
+        crate::fmt::from_quantity_str(src)
+    }
+
     /// Converts a prefixed string slice in a base determined by the prefix to
     /// an integer.
     ///
@@ -150,9 +264,234 @@ impl U256 {
     /// assert_eq!(U256::from_str_prefixed("0xa"), Ok(U256::new(10)));
     /// ```
     pub fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_prefixed_detailed(src).map_err(ParseIntError::from)
+    }
+
+    /// Converts a prefixed string slice in a base determined by the prefix to
+    /// an integer, returning a detailed error on failure.
+    ///
+    /// This behaves exactly like [`U256::from_str_prefixed`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let err = U256::from_str_prefixed_detailed("10_000").unwrap_err();
+    /// assert_eq!(err.offset(), 2);
+    /// ```
+    pub fn from_str_prefixed_detailed(src: &str) -> Result<Self, ParseError> {
         crate::fmt::from_str_prefixed(src)
     }
 
+    /// Converts a string slice to an integer, accepting the same extended
+    /// grammar that humans commonly use for writing large numbers.
+    ///
+    /// In addition to plain decimal numbers, this accepts:
+    ///
+    /// * a leading `+` sign
+    /// * `0b`, `0o` and `0x` prefixes in either letter case, for binary,
+    ///   octal and hexadecimal numbers
+    /// * `_` digit separators after the first digit or the prefix
+    /// * decimal scientific notation, such as `1.5e18`, `2E6` or `5000e-3`, as
+    ///   long as the result is an integer
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::from_str_lenient("1_000_000"), Ok(U256::new(1_000_000)));
+    /// assert_eq!(U256::from_str_lenient("0XFF"), Ok(U256::new(255)));
+    /// assert_eq!(U256::from_str_lenient("+0b1010"), Ok(U256::new(10)));
+    /// assert_eq!(
+    ///     U256::from_str_lenient("1.5e18"),
+    ///     Ok(U256::new(1_500_000_000_000_000_000)),
+    /// );
+    /// assert_eq!(U256::from_str_lenient("5000e-3"), Ok(U256::new(5)));
+    /// assert!(U256::from_str_lenient("1.5e0").is_err());
+    /// ```
+    pub fn from_str_lenient(src: &str) -> Result<Self, ParseError> {
+        crate::fmt::from_str_lenient(src)
+    }
+
+    /// Formats the integer in the specified radix into a stack-allocated
+    /// buffer, without requiring an allocator.
+    ///
+    /// Digits above 9 are represented with lower-case letters, and no prefix
+    /// is added.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::new(255).to_str_radix_buf(16).as_str(), "ff");
+    /// assert_eq!(U256::new(71).to_str_radix_buf(36).as_str(), "1z");
+    /// ```
+    pub fn to_str_radix_buf(self, radix: u32) -> FormatBuffer<256> {
+        FormatBuffer::radix(self, false, radix)
+    }
+
+    /// Formats the integer as a `0x`-prefixed, lower-case hexadecimal string
+    /// zero-padded to 64 digits.
+    ///
+    /// The returned bytes are always valid ASCII.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let hex = U256::new(0xbeef).to_hex_array();
+    /// assert_eq!(
+    ///     core::str::from_utf8(&hex).unwrap(),
+    ///     "0x000000000000000000000000000000000000000000000000000000000000beef",
+    /// );
+    /// ```
+    pub fn to_hex_array(self) -> [u8; 66] {
+        crate::fmt::to_hex_array(self)
+    }
+
+    /// Formats the integer as a decimal string into a stack-allocated buffer,
+    /// which is large enough to hold any 256-bit value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::new(1337).to_dec_array().as_str(), "1337");
+    /// assert_eq!(U256::ZERO.to_dec_array().as_str(), "0");
+    /// assert_eq!(U256::MAX.to_dec_array().len(), 78);
+    /// ```
+    pub fn to_dec_array(self) -> FormatBuffer<78> {
+        FormatBuffer::radix(self, false, 10)
+    }
+
+    /// Formats the integer as a decimal string zero-padded to 78 digits,
+    /// which is enough to hold any 256-bit value.
+    ///
+    /// The returned bytes are always valid ASCII.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let dec = U256::new(1337).to_dec_array_padded();
+    /// let dec = core::str::from_utf8(&dec).unwrap();
+    /// assert_eq!(dec.trim_start_matches('0'), "1337");
+    /// assert_eq!(dec.len(), 78);
+    /// ```
+    pub fn to_dec_array_padded(self) -> [u8; 78] {
+        crate::fmt::to_dec_array_padded(self)
+    }
+
+    /// Precomputes a reciprocal of `self` for efficiently dividing many values
+    /// by it. See [`Divisor256`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `self` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let d = U256::new(1_000).divisor();
+    /// assert_eq!(U256::new(123_456) / d, 123);
+    /// assert_eq!(U256::new(123_456) % d, 456);
+    /// ```
+    pub fn divisor(self) -> Divisor256 {
+        Divisor256::new(self)
+    }
+
+    /// Calculates the quotient and remainder of `self` divided by a 64-bit
+    /// divisor.
+    ///
+    /// This performs a long division over the 64-bit limbs of `self`, with a
+    /// single 128-by-64-bit division per limb. On `x86_64`, each of these uses
+    /// the native `div` instruction unless the `safe` feature is enabled.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `d` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let wei = U256::from_words(1, 0);
+    /// let (ether, rest) = wei.div_rem_u64(1_000_000_000_000_000_000);
+    /// assert_eq!(ether, 340_282_366_920_938_463_463);
+    /// assert_eq!(rest, 374_607_431_768_211_456);
+    /// ```
+    pub fn div_rem_u64(self, d: u64) -> (Self, u64) {
+        assert!(d != 0, "attempt to divide by zero");
+        let mut limbs = self.into_limbs();
+        let mut r = 0;
+        for limb in limbs.iter_mut().rev() {
+            // Skip the division for leading limbs that are smaller than the
+            // divisor, which are common for small dividends.
+            (*limb, r) = if r == 0 && *limb < d {
+                (0, *limb)
+            } else {
+                udiv128_by_64(r, *limb, d)
+            };
+        }
+        (U256::from_limbs(limbs), r)
+    }
+
+    /// Calculates the quotient and remainder of `self` divided by a 128-bit
+    /// divisor.
+    ///
+    /// Divisors that fit in 64 bits use [`U256::div_rem_u64`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `d` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::MAX.div_rem_u128(10_000), (U256::MAX / 10_000, 9_935));
+    /// assert_eq!(
+    ///     U256::MAX.div_rem_u128(u128::MAX),
+    ///     (U256::from_words(1, 1), 0),
+    /// );
+    /// ```
+    pub fn div_rem_u128(self, d: u128) -> (Self, u128) {
+        if let Ok(d) = u64::try_from(d) {
+            let (q, r) = self.div_rem_u64(d);
+            return (q, r as _);
+        }
+        let mut r = U256::ZERO;
+        let q = crate::intrinsics::udivmod(&self, &U256::new(d), Some(&mut r));
+        (q, *r.low())
+    }
+
     /// Cast to a primitive `i8`.
     pub const fn as_i8(self) -> i8 {
         let (_, lo) = self.into_words();
@@ -247,6 +586,97 @@ impl U256 {
         let (hi, lo) = self.into_words();
         (hi as f64) * f64::from_bits(HI) + (lo as f64)
     }
+
+    /// Converts a value to an integer, returning a detailed error on failure.
+    ///
+    /// This behaves exactly like [`TryFrom::try_from`], except that the
+    /// returned [`TryFromIntError`] additionally indicates whether the value
+    /// was too large or too small.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::{I256, U256};
+    /// use core::num::IntErrorKind;
+    ///
+    /// assert_eq!(U256::try_from_detailed(42_i8), Ok(U256::new(42)));
+    /// let err = U256::try_from_detailed(I256::MIN).unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::NegOverflow);
+    /// ```
+    pub fn try_from_detailed<T>(value: T) -> Result<Self, TryFromIntError>
+    where
+        T: Integer,
+        Self: TryFrom<T>,
+    {
+        Self::try_from(value).map_err(|_| {
+            if value.is_negative() {
+                TryFromIntError::neg_overflow()
+            } else {
+                TryFromIntError::pos_overflow()
+            }
+        })
+    }
+
+    /// Converts the integer to another type, returning a detailed error on
+    /// failure.
+    ///
+    /// This behaves exactly like [`TryFrom::try_from`], except that the
+    /// returned [`TryFromIntError`] additionally indicates whether the value
+    /// was too large or too small.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// use core::num::IntErrorKind;
+    ///
+    /// assert_eq!(U256::new(42).try_into_detailed::<u8>(), Ok(42));
+    /// let err = U256::MAX.try_into_detailed::<u8>().unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::PosOverflow);
+    /// ```
+    pub fn try_into_detailed<T>(self) -> Result<T, TryFromIntError>
+    where
+        T: TryFrom<Self>,
+    {
+        T::try_from(self).map_err(|_| TryFromIntError::pos_overflow())
+    }
+}
+
+/// Divides the 128-bit value `hi:lo` by `d`, returning the quotient and the
+/// remainder.
+///
+/// The caller must ensure that `hi < d`, so that the quotient fits in 64 bits.
+/// This always holds for the running remainder of a long division.
+#[inline(always)]
+fn udiv128_by_64(hi: u64, lo: u64, d: u64) -> (u64, u64) {
+    debug_assert!(hi < d);
+
+    #[cfg(all(target_arch = "x86_64", not(feature = "safe")))]
+    {
+        let (q, r);
+        // SAFETY: Since `hi < d`, the divisor is non-zero and the quotient fits
+        // in 64 bits, so the `div` instruction never raises a divide error.
+        unsafe {
+            core::arch::asm!(
+                "div {d}",
+                d = in(reg) d,
+                inout("rax") lo => q,
+                inout("rdx") hi => r,
+                options(pure, nomem, nostack),
+            );
+        }
+        (q, r)
+    }
+    #[cfg(not(all(target_arch = "x86_64", not(feature = "safe"))))]
+    {
+        let x = ((hi as u128) << 64) | (lo as u128);
+        let q = (x / d as u128) as u64;
+        (q, lo.wrapping_sub(q.wrapping_mul(d)))
+    }
 }
 
 #[cfg(test)]
@@ -258,4 +688,47 @@ mod tests {
     fn converts_to_f64() {
         assert_eq!(U256::from_words(1, 0).as_f64(), 2.0f64.powi(128))
     }
+
+    #[test]
+    fn div_rem_small_divisors() {
+        let values = [
+            U256::ZERO,
+            U256::new(9_999),
+            U256::new(u64::MAX as _),
+            U256::new(u128::MAX),
+            U256::from_words(0x0001_7eb0_2a11_f4a9_443a_bc50_58e1_c2c2, 42),
+            U256::MAX,
+        ];
+        for x in values {
+            for d in [1, 3, 10_000, 1_000_000_000_000_000_000, u64::MAX] {
+                let (q, r) = x.div_rem_u64(d);
+                assert!(r < d);
+                assert_eq!(q * U256::new(d as _) + U256::new(r as _), x);
+                assert_eq!((x / d as u128, x % d as u128), (q, U256::new(r as _)));
+                assert_eq!(x.div_rem_u128(d as _), (q, r as _));
+            }
+            for d in [u64::MAX as u128 + 1, 10_u128.pow(36), u128::MAX] {
+                let (q, r) = x.div_rem_u128(d);
+                assert!(r < d);
+                assert_eq!(q * U256::new(d) + U256::new(r), x);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_rem_u64_by_zero() {
+        let _ = U256::ONE.div_rem_u64(0);
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy_bytes() {
+        use zerocopy::{FromBytes, FromZeros, IntoBytes};
+
+        let value = U256::from_words(1, 2);
+        assert_eq!(value.as_bytes(), &value.to_ne_bytes());
+        assert_eq!(U256::read_from_bytes(value.as_bytes()), Ok(value));
+        assert_eq!(U256::new_zeroed(), U256::ZERO);
+    }
 }
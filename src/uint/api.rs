@@ -2,11 +2,8 @@
 //! standard library API for `uN` types.
 
 use super::U256;
-use crate::{fmt, intrinsics};
-use core::{
-    mem::{self, MaybeUninit},
-    num::ParseIntError,
-};
+use crate::{error::ParseError, fmt, intrinsics};
+use core::{mem, num::ParseIntError};
 
 impl U256 {
     /// The smallest value that can be represented by this integer type.
@@ -70,9 +67,77 @@ impl U256 {
     /// ```
     #[inline]
     pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+        fmt::from_str_radix(src, radix, None).map_err(ParseIntError::from)
+    }
+
+    /// Converts a string slice in a given base to an integer, returning a
+    /// detailed error on failure.
+    ///
+    /// This behaves exactly like [`U256::from_str_radix`], except that the
+    /// returned [`ParseError`] additionally includes the byte offset at which
+    /// parsing failed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let err = U256::from_str_radix_detailed("12z4", 10).unwrap_err();
+    /// assert_eq!(err.offset(), 2);
+    /// ```
+    #[inline]
+    pub fn from_str_radix_detailed(src: &str, radix: u32) -> Result<Self, ParseError> {
         fmt::from_str_radix(src, radix, None)
     }
 
+    /// Converts a byte slice in a given base to an integer.
+    ///
+    /// This behaves exactly like [`U256::from_str_radix_detailed`], but parses
+    /// directly from ASCII bytes without requiring the input to be valid UTF-8
+    /// first. Error offsets are in bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::from_ascii_radix(b"A", 16), Ok(U256::new(10)));
+    /// assert!(U256::from_ascii_radix(b"1\xff", 16).is_err());
+    /// ```
+    #[inline]
+    pub fn from_ascii_radix(src: &[u8], radix: u32) -> Result<Self, ParseError> {
+        fmt::from_ascii_radix(src, radix, None)
+    }
+
+    /// Converts a byte slice in base 10 to an integer.
+    ///
+    /// This behaves exactly like parsing a string slice with
+    /// [`str::parse`], but parses directly from ASCII bytes without requiring
+    /// the input to be valid UTF-8 first, and returns a detailed error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// assert_eq!(U256::from_ascii(b"+42"), Ok(U256::new(42)));
+    /// ```
+    #[inline]
+    pub fn from_ascii(src: &[u8]) -> Result<Self, ParseError> {
+        fmt::from_ascii_radix(src, 10, None)
+    }
+
     /// Returns the number of ones in the binary representation of `self`.
     ///
     /// # Examples
@@ -198,9 +263,7 @@ impl U256 {
                           without modifying the original"]
     #[inline]
     pub fn rotate_left(self, n: u32) -> Self {
-        let mut r = MaybeUninit::uninit();
-        intrinsics::signed::urol3(&mut r, &self, n);
-        unsafe { r.assume_init() }
+        intrinsics::signed::urol(&self, n)
     }
 
     /// Shifts the bits to the right by a specified amount, `n`, wrapping the
@@ -226,9 +289,7 @@ impl U256 {
                           without modifying the original"]
     #[inline]
     pub fn rotate_right(self, n: u32) -> Self {
-        let mut r = MaybeUninit::uninit();
-        intrinsics::signed::uror3(&mut r, &self, n);
-        unsafe { r.assume_init() }
+        intrinsics::signed::uror(&self, n)
     }
 
     /// Reverses the byte order of the integer.
@@ -685,7 +746,6 @@ impl U256 {
     /// assert_eq!(U256::new(100).saturating_add(U256::new(1)), U256::new(101));
     /// assert_eq!(U256::MAX.saturating_add(U256::new(127)), U256::MAX);
     /// ```
-
     #[must_use = "this returns the result of the operation, \
                   without modifying the original"]
     #[inline]
@@ -796,9 +856,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_add(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::uadd3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::uadd(&self, &rhs)
     }
 
     /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around
@@ -817,9 +875,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_sub(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::usub3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::usub(&self, &rhs)
     }
 
     /// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping
@@ -841,9 +897,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_mul(self, rhs: Self) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::umul3(&mut result, &self, &rhs);
-        unsafe { result.assume_init() }
+        intrinsics::signed::umul(&self, &rhs)
     }
 
     /// Wrapping (modular) division. Computes `self / rhs`. Wrapped division on
@@ -986,9 +1040,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_shl(self, rhs: u32) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::ushl3(&mut result, &self, rhs & 0xff);
-        unsafe { result.assume_init() }
+        intrinsics::signed::ushl(&self, rhs & 0xff)
     }
 
     /// Panic-free bitwise shift-right; yields `self >> mask(rhs)`, where `mask`
@@ -1015,9 +1067,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn wrapping_shr(self, rhs: u32) -> Self {
-        let mut result = MaybeUninit::uninit();
-        intrinsics::signed::ushr3(&mut result, &self, rhs & 0xff);
-        unsafe { result.assume_init() }
+        intrinsics::signed::ushr(&self, rhs & 0xff)
     }
 
     /// Wrapping (modular) exponentiation. Computes `self.pow(exp)`, wrapping
@@ -1082,9 +1132,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::uaddc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::uaddo(&self, &rhs)
     }
 
     /// Calculates `self` - `rhs`
@@ -1106,9 +1154,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::usubc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::usubo(&self, &rhs)
     }
 
     /// Calculates the multiplication of `self` and `rhs`.
@@ -1136,9 +1182,7 @@ impl U256 {
                   without modifying the original"]
     #[inline]
     pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
-        let mut result = MaybeUninit::uninit();
-        let overflow = intrinsics::signed::umulc(&mut result, &self, &rhs);
-        (unsafe { result.assume_init() }, overflow)
+        intrinsics::signed::umulo(&self, &rhs)
     }
 
     /// Calculates the divisor when `self` is divided by `rhs`.
@@ -1615,7 +1659,38 @@ impl U256 {
     /// ```
     #[inline]
     pub fn to_ne_bytes(self) -> [u8; mem::size_of::<Self>()] {
-        unsafe { mem::transmute(self) }
+        #[cfg(not(feature = "safe"))]
+        // SAFETY: integers are plain old datatypes so we can always transmute them to
+        // arrays of bytes
+        return unsafe { mem::transmute::<Self, [u8; 32]>(self) };
+        #[cfg(feature = "safe")]
+        return intrinsics::cast::words_to_ne_bytes(self.0);
+    }
+
+    /// Return a reference to the memory representation of this integer as a
+    /// byte array in little endian byte order, without copying.
+    ///
+    /// This is only available on little endian targets, where the in-memory
+    /// representation of the integer is little endian, and when the `safe`
+    /// feature is disabled. Use [`to_le_bytes`] for a portable alternative.
+    ///
+    /// [`to_le_bytes`]: #method.to_le_bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ethnum::U256;
+    /// let value = U256::new(0x1234);
+    /// assert_eq!(value.as_le_bytes(), &value.to_le_bytes());
+    /// assert_eq!(value.as_le_bytes()[..2], [0x34, 0x12]);
+    /// ```
+    #[cfg(all(target_endian = "little", not(feature = "safe")))]
+    #[inline]
+    pub const fn as_le_bytes(&self) -> &[u8; mem::size_of::<Self>()] {
+        // SAFETY: integers are plain old datatypes without padding, and
+        // byte arrays have no alignment requirements, so we can always
+        // reinterpret a reference to an integer as a reference to its bytes.
+        unsafe { &*(self as *const Self).cast() }
     }
 
     /// Create an integer value from its representation as a byte array in big
@@ -1743,6 +1818,10 @@ impl U256 {
     /// ```
     #[inline]
     pub fn from_ne_bytes(bytes: [u8; mem::size_of::<Self>()]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        #[cfg(not(feature = "safe"))]
+        // SAFETY: integers are plain old datatypes so we can always transmute to them
+        return unsafe { mem::transmute::<[u8; 32], Self>(bytes) };
+        #[cfg(feature = "safe")]
+        return Self(intrinsics::cast::words_from_ne_bytes(bytes));
     }
 }
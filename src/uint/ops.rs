@@ -16,15 +16,15 @@ use crate::intrinsics::signed::*;
 
 impl_ops! {
     for U256 | u128 {
-        add => uadd2, uadd3, uaddc;
-        mul => umul2, umul3, umulc;
-        sub => usub2, usub3, usubc;
+        add => uadd2, uadd, uaddo;
+        mul => umul2, umul, umulo;
+        sub => usub2, usub, usubo;
 
-        div => udiv2, udiv3;
-        rem => urem2, urem3;
+        div => udiv2, udiv;
+        rem => urem2, urem;
 
-        shl => ushl2, ushl3;
-        shr => ushr2, ushr3;
+        shl => ushl2, ushl;
+        shr => ushr2, ushr;
     }
 }
 
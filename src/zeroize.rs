@@ -0,0 +1,43 @@
+//! Implementation of `zeroize` traits for 256-bit integer types.
+//!
+//! Since both integer types are `Copy` and default to zero, they implement
+//! [`DefaultIsZeroes`], which in turn provides a [`Zeroize`] implementation
+//! that volatile-writes the default value. This allows secrets to be wrapped
+//! in [`Zeroizing`] so they are wiped from memory on drop:
+//!
+//! ```
+//! # use ethnum::U256;
+//! use zeroize::{Zeroize, Zeroizing};
+//!
+//! let mut secret = U256::MAX;
+//! secret.zeroize();
+//! assert_eq!(secret, U256::ZERO);
+//!
+//! let key = Zeroizing::new(U256::new(42));
+//! assert_eq!(*key, 42);
+//! ```
+//!
+//! [`Zeroizing`]: ::zeroize::Zeroizing
+
+use crate::{int::I256, uint::U256};
+use ::zeroize::DefaultIsZeroes;
+
+impl DefaultIsZeroes for I256 {}
+impl DefaultIsZeroes for U256 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::zeroize::{Zeroize, Zeroizing};
+
+    #[test]
+    fn zeroize() {
+        let mut values = [I256::MIN, I256::MINUS_ONE, I256::MAX];
+        values.zeroize();
+        assert_eq!(values, [I256::ZERO; 3]);
+
+        let mut value = Zeroizing::new(U256::MAX);
+        *value -= 1;
+        assert_eq!(*value, U256::MAX - 1);
+    }
+}